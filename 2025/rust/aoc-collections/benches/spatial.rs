@@ -0,0 +1,179 @@
+//! The k-d tree against scanning every point, on
+//! as many points as day 8 has junction boxes.
+
+use aoc_collections::spatial::{
+    KdTree3, Neighbour, Point, distance_squared,
+};
+
+fn main() {
+    divan::main();
+}
+
+const POINTS: usize = 1000;
+const SIZE: i64 = 100_000;
+
+/// Deterministic pseudo random points spread
+/// like day 8's junction boxes.
+fn points() -> Vec<Point<3>> {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state % SIZE as u64) as i64
+    };
+    (0..POINTS)
+        .map(|_| std::array::from_fn(|_| next()))
+        .collect()
+}
+
+/// The `k` closest points to `query` by checking
+/// every point.
+fn brute_force(
+    points: &[Point<3>],
+    query: &Point<3>,
+    k: usize,
+) -> Vec<Neighbour> {
+    let mut neighbours = points
+        .iter()
+        .enumerate()
+        .map(|(index, point)| Neighbour {
+            distance_squared: distance_squared(
+                point, query,
+            ),
+            index,
+        })
+        .collect::<Vec<_>>();
+    if k < neighbours.len() {
+        neighbours.select_nth_unstable(k);
+        neighbours.truncate(k);
+    }
+    neighbours.sort_unstable();
+    neighbours
+}
+
+#[divan::bench]
+fn build(bencher: divan::Bencher) {
+    let points = points();
+    bencher.bench(|| {
+        KdTree3::new(
+            divan::black_box(&points).iter().copied(),
+        )
+    });
+}
+
+/// The closest other point to every point.
+mod nearest {
+    use super::*;
+
+    #[divan::bench]
+    fn kd_tree(bencher: divan::Bencher) {
+        let points = points();
+        let tree = KdTree3::new(points.iter().copied());
+        bencher.bench(|| {
+            divan::black_box(&points)
+                .iter()
+                .map(|point| {
+                    // the first is the point itself
+                    tree.k_nearest(point, 2)[1]
+                        .distance_squared
+                })
+                .sum::<i64>()
+        });
+    }
+
+    #[divan::bench]
+    fn brute_force(bencher: divan::Bencher) {
+        let points = points();
+        bencher.bench(|| {
+            let points = divan::black_box(&points);
+            (0..points.len())
+                .map(|a| {
+                    (0..points.len())
+                        .filter(|&b| b != a)
+                        .map(|b| {
+                            distance_squared(
+                                &points[a], &points[b],
+                            )
+                        })
+                        .min()
+                        .unwrap_or_default()
+                })
+                .sum::<i64>()
+        });
+    }
+}
+
+/// The 10 closest points to every point.
+mod k_nearest {
+    use super::*;
+
+    const K: usize = 10;
+
+    #[divan::bench]
+    fn kd_tree(bencher: divan::Bencher) {
+        let points = points();
+        let tree = KdTree3::new(points.iter().copied());
+        bencher.bench(|| {
+            divan::black_box(&points)
+                .iter()
+                .map(|point| tree.k_nearest(point, K).len())
+                .sum::<usize>()
+        });
+    }
+
+    #[divan::bench]
+    fn brute_force(bencher: divan::Bencher) {
+        let points = points();
+        bencher.bench(|| {
+            divan::black_box(&points)
+                .iter()
+                .map(|point| {
+                    super::brute_force(&points, point, K)
+                        .len()
+                })
+                .sum::<usize>()
+        });
+    }
+}
+
+/// The points within a tenth of the cube's side
+/// of every point.
+mod within {
+    use super::*;
+
+    const RADIUS: i64 = SIZE / 10;
+
+    #[divan::bench]
+    fn kd_tree(bencher: divan::Bencher) {
+        let points = points();
+        let tree = KdTree3::new(points.iter().copied());
+        bencher.bench(|| {
+            divan::black_box(&points)
+                .iter()
+                .map(|point| {
+                    tree.within(point, RADIUS).len()
+                })
+                .sum::<usize>()
+        });
+    }
+
+    #[divan::bench]
+    fn brute_force(bencher: divan::Bencher) {
+        let points = points();
+        bencher.bench(|| {
+            divan::black_box(&points)
+                .iter()
+                .map(|point| {
+                    points
+                        .iter()
+                        .filter(|other| {
+                            distance_squared(point, other)
+                                <= RADIUS.pow(2)
+                        })
+                        .count()
+                })
+                .sum::<usize>()
+        });
+    }
+}
@@ -0,0 +1,185 @@
+/// A union-find over the elements `0..len`,
+/// tracking which elements are connected.
+///
+/// Unions attach the smaller component to the
+/// larger one and [`find`](DisjointSet::find)
+/// compresses the paths it walks, so both take
+/// nearly constant amortised time.
+#[derive(Clone, Debug)]
+pub struct DisjointSet {
+    parents: Vec<usize>,
+    /// the size of the component, only kept up
+    /// to date for roots
+    sizes: Vec<usize>,
+    components: usize,
+}
+
+impl DisjointSet {
+    /// `len` elements, each in a component of
+    /// its own.
+    pub fn new(len: usize) -> Self {
+        DisjointSet {
+            parents: (0..len).collect(),
+            sizes: vec![1; len],
+            components: len,
+        }
+    }
+
+    /// The number of elements.
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// The number of disjoint components.
+    pub fn components(&self) -> usize {
+        self.components
+    }
+
+    /// The representative of the component
+    /// holding `element`.
+    ///
+    /// # Panics
+    ///
+    /// When `element` is out of bounds.
+    pub fn find(&mut self, element: usize) -> usize {
+        let mut root = element;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        let mut current = element;
+        while current != root {
+            current = std::mem::replace(
+                &mut self.parents[current],
+                root,
+            );
+        }
+        root
+    }
+
+    /// Merges the components of `a` and `b`,
+    /// returning `false` when they were already
+    /// connected.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (large, small) =
+            if self.sizes[a] < self.sizes[b] {
+                (b, a)
+            } else {
+                (a, b)
+            };
+        self.parents[small] = large;
+        self.sizes[large] += self.sizes[small];
+        self.components -= 1;
+        true
+    }
+
+    pub fn connected(
+        &mut self,
+        a: usize,
+        b: usize,
+    ) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// The number of elements in the component
+    /// holding `element`.
+    pub fn size(&mut self, element: usize) -> usize {
+        let root = self.find(element);
+        self.sizes[root]
+    }
+
+    /// The size of every component, in the order
+    /// of their representatives.
+    pub fn component_sizes(
+        &self,
+    ) -> impl Iterator<Item = usize> + '_ {
+        self.parents
+            .iter()
+            .zip(&self.sizes)
+            .enumerate()
+            .filter(|&(element, (&parent, _))| {
+                parent == element
+            })
+            .map(|(_, (_, &size))| size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let set = DisjointSet::new(3);
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.components(), 3);
+        assert_eq!(
+            set.component_sizes().collect::<Vec<_>>(),
+            [1, 1, 1]
+        );
+        assert!(DisjointSet::new(0).is_empty());
+    }
+
+    #[test]
+    fn test_union() {
+        let mut set = DisjointSet::new(6);
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 3));
+        assert!(set.union(1, 3));
+        assert!(!set.union(0, 2));
+
+        assert!(set.connected(0, 3));
+        assert!(!set.connected(0, 4));
+        assert_eq!(set.size(2), 4);
+        assert_eq!(set.size(5), 1);
+        assert_eq!(set.components(), 3);
+        let mut sizes =
+            set.component_sizes().collect::<Vec<_>>();
+        sizes.sort();
+        assert_eq!(sizes, [1, 1, 4]);
+    }
+
+    #[rstest]
+    #[case::forwards(&[(0, 1), (1, 2), (2, 3), (3, 4)])]
+    #[case::backwards(&[(3, 4), (2, 3), (1, 2), (0, 1)])]
+    #[case::star(&[(0, 1), (0, 2), (0, 3), (0, 4)])]
+    fn test_chain(#[case] unions: &[(usize, usize)]) {
+        let mut set = DisjointSet::new(5);
+        for &(a, b) in unions {
+            assert!(set.union(a, b));
+        }
+        assert_eq!(set.components(), 1);
+        let root = set.find(0);
+        assert!(
+            (0..5).all(|element| {
+                set.find(element) == root
+            })
+        );
+        assert_eq!(
+            set.component_sizes().collect::<Vec<_>>(),
+            [5]
+        );
+    }
+
+    #[test]
+    fn test_find_compresses_paths() {
+        let mut set = DisjointSet::new(4);
+        // equal sizes keep the first root, so
+        // this builds the chain 3 -> 2 -> 0
+        set.union(0, 1);
+        set.union(2, 3);
+        set.union(0, 2);
+        assert_eq!(set.parents, [0, 0, 0, 2]);
+        assert_eq!(set.find(3), 0);
+        assert_eq!(set.parents, [0, 0, 0, 0]);
+    }
+}
@@ -0,0 +1,496 @@
+use std::{fmt::Debug, ops::RangeInclusive};
+
+/// The integers an [`IntervalSet`] can hold.
+pub trait Integer: Copy + Ord + Debug {
+    fn successor(self) -> Option<Self>;
+    fn predecessor(self) -> Option<Self>;
+    /// The number of integers in `start..=end`,
+    /// which can be one more than `Self` holds.
+    fn count(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_integer {
+    ($($integer:ty),*) => {$(
+        impl Integer for $integer {
+            fn successor(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn predecessor(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            fn count(start: Self, end: Self) -> u128 {
+                start.abs_diff(end) as u128 + 1
+            }
+        }
+    )*};
+}
+
+impl_integer!(
+    u8, u16, u32, u64, usize, i8, i16, i32, i64, isize
+);
+
+/// A set of integers stored as the inclusive
+/// ranges it covers.
+///
+/// The ranges are kept sorted, disjoint and
+/// coalesced: ranges that overlap or touch, like
+/// `3-5` and `6-8`, are stored as one, so
+/// membership is a binary search.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { ranges: Vec::new() }
+    }
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of disjoint ranges.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of integers in the set.
+    pub fn covered_len(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(|&(start, end)| T::count(start, end))
+            .sum()
+    }
+
+    /// Adds every integer in `range`, merging it
+    /// with the ranges it overlaps or touches.
+    /// Empty ranges are ignored.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        // the ranges before `first` end more than
+        // one before `start`, the ones from `last`
+        // on start more than one after `end`
+        let first =
+            self.ranges.partition_point(|&(_, other)| {
+                other
+                    .successor()
+                    .is_some_and(|next| next < start)
+            });
+        let last =
+            self.ranges.partition_point(|&(other, _)| {
+                other
+                    .predecessor()
+                    .is_none_or(|previous| previous <= end)
+            });
+        let merged = if first < last {
+            (
+                start.min(self.ranges[first].0),
+                end.max(self.ranges[last - 1].1),
+            )
+        } else {
+            (start, end)
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self
+            .ranges
+            .partition_point(|&(_, end)| end < value);
+        self.ranges
+            .get(index)
+            .is_some_and(|&(start, _)| start <= value)
+    }
+
+    /// The ranges of the set, in order.
+    pub fn ranges(
+        &self,
+    ) -> impl ExactSizeIterator<Item = RangeInclusive<T>> + '_
+    {
+        self.ranges.iter().map(|&(start, end)| start..=end)
+    }
+
+    /// The ranges missing between the first and
+    /// the last range of the set, in order.
+    pub fn gaps(
+        &self,
+    ) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.windows(2).filter_map(|pair| {
+            Some(
+                pair[0].1.successor()?
+                    ..=pair[1].0.predecessor()?,
+            )
+        })
+    }
+
+    /// The integers in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut ranges = self
+            .ranges
+            .iter()
+            .chain(&other.ranges)
+            .copied()
+            .collect::<Vec<_>>();
+        ranges.sort_unstable();
+        IntervalSet {
+            ranges: coalesce(ranges),
+        }
+    }
+
+    /// The integers in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut a, mut b) =
+            (self.ranges.iter(), other.ranges.iter());
+        let (mut next_a, mut next_b) = (a.next(), b.next());
+        while let (
+            Some(&(a_start, a_end)),
+            Some(&(b_start, b_end)),
+        ) = (next_a, next_b)
+        {
+            let (start, end) =
+                (a_start.max(b_start), a_end.min(b_end));
+            if start <= end {
+                ranges.push((start, end));
+            }
+            if a_end < b_end {
+                next_a = a.next();
+            } else {
+                next_b = b.next();
+            }
+        }
+        // a gap in either set is a gap between the
+        // pieces, so they are already coalesced
+        IntervalSet { ranges }
+    }
+
+    /// The integers in this set but not in
+    /// `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut others = other.ranges.iter().peekable();
+        for &(mut start, end) in &self.ranges {
+            // skip what ends before this range
+            while others
+                .next_if(|&&(_, other_end)| {
+                    other_end < start
+                })
+                .is_some()
+            {}
+            let mut remaining = true;
+            while let Some(&&(other_start, other_end)) =
+                others.peek()
+            {
+                if other_start > end {
+                    break;
+                }
+                if other_start > start
+                    && let Some(before) =
+                        other_start.predecessor()
+                {
+                    ranges.push((start, before));
+                }
+                match other_end.successor() {
+                    Some(after) if other_end < end => {
+                        start = after;
+                        others.next();
+                    }
+                    // the other range reaches past this
+                    // one, it may cut the next one too
+                    _ => {
+                        remaining = false;
+                        break;
+                    }
+                }
+            }
+            if remaining {
+                ranges.push((start, end));
+            }
+        }
+        IntervalSet { ranges }
+    }
+}
+
+/// Merges sorted ranges that overlap or touch.
+fn coalesce<T: Integer>(
+    sorted: Vec<(T, T)>,
+) -> Vec<(T, T)> {
+    let mut ranges: Vec<(T, T)> =
+        Vec::with_capacity(sorted.len());
+    for (start, end) in sorted {
+        match ranges.last_mut() {
+            Some((_, last_end))
+                if last_end
+                    .successor()
+                    .is_none_or(|next| next >= start) =>
+            {
+                *last_end = end.max(*last_end);
+            }
+            _ => ranges.push((start, end)),
+        }
+    }
+    ranges
+}
+
+impl<T: Integer> FromIterator<RangeInclusive<T>>
+    for IntervalSet<T>
+{
+    fn from_iter<I>(ranges: I) -> Self
+    where
+        I: IntoIterator<Item = RangeInclusive<T>>,
+    {
+        let mut ranges = ranges
+            .into_iter()
+            .map(RangeInclusive::into_inner)
+            .filter(|(start, end)| start <= end)
+            .collect::<Vec<_>>();
+        ranges.sort_unstable();
+        IntervalSet {
+            ranges: coalesce(ranges),
+        }
+    }
+}
+
+impl<T: Integer> Extend<RangeInclusive<T>>
+    for IntervalSet<T>
+{
+    fn extend<I>(&mut self, ranges: I)
+    where
+        I: IntoIterator<Item = RangeInclusive<T>>,
+    {
+        for range in ranges {
+            self.insert(range);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    use super::*;
+
+    /// Which of the 256 `u8`s are in the set, the
+    /// naive set the properties check against.
+    type Bits = [bool; 256];
+
+    fn bits(ranges: &[(u8, u8)]) -> Bits {
+        let mut bits = [false; 256];
+        for &(start, end) in ranges {
+            for value in start..=end {
+                bits[usize::from(value)] = true;
+            }
+        }
+        bits
+    }
+
+    /// Which values `set` holds.
+    fn bits_of(set: &IntervalSet<u8>) -> Bits {
+        bits(
+            &set.ranges()
+                .map(RangeInclusive::into_inner)
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// The set holding exactly `bits`, built one
+    /// value at a time.
+    fn from_bits(bits: &Bits) -> IntervalSet<u8> {
+        let mut set = IntervalSet::new();
+        for value in 0..=u8::MAX {
+            if bits[usize::from(value)] {
+                set.insert(value..=value);
+            }
+        }
+        set
+    }
+
+    fn set(ranges: &[(u8, u8)]) -> IntervalSet<u8> {
+        ranges
+            .iter()
+            .map(|&(start, end)| start..=end)
+            .collect()
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut set = IntervalSet::new();
+        set.insert(10..=14);
+        set.insert(3..=5);
+        set.insert(16..=20);
+        assert_eq!(set.len(), 3);
+        set.insert(12..=18);
+        assert_eq!(
+            set.ranges().collect::<Vec<_>>(),
+            [3..=5, 10..=20]
+        );
+        set.insert(6..=9);
+        assert_eq!(
+            set.ranges().collect::<Vec<_>>(),
+            [3..=20]
+        );
+        #[allow(clippy::reversed_empty_ranges)]
+        set.insert(30..=25);
+        assert_eq!(set.covered_len(), 18);
+    }
+
+    #[rstest]
+    #[case(2, false)]
+    #[case(3, true)]
+    #[case(5, true)]
+    #[case(8, false)]
+    #[case(10, true)]
+    #[case(21, false)]
+    fn test_contains(
+        #[case] value: u64,
+        #[case] expected: bool,
+    ) {
+        let set = IntervalSet::from_iter([
+            3..=5,
+            10..=14,
+            16..=20,
+            12..=18,
+        ]);
+        assert_eq!(set.contains(value), expected);
+    }
+
+    #[test]
+    fn test_gaps() {
+        let set = IntervalSet::from_iter([
+            3..=5,
+            10..=14,
+            20..=20,
+        ]);
+        assert_eq!(
+            set.gaps().collect::<Vec<_>>(),
+            [6..=9, 15..=19]
+        );
+        assert_eq!(
+            IntervalSet::<u64>::new().gaps().count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = IntervalSet::from_iter([1..=10, 20..=30]);
+        let b = IntervalSet::from_iter([5..=25]);
+        assert_eq!(
+            a.union(&b).ranges().collect::<Vec<_>>(),
+            [1..=30]
+        );
+        assert_eq!(
+            a.intersection(&b).ranges().collect::<Vec<_>>(),
+            [5..=10, 20..=25]
+        );
+        assert_eq!(
+            a.difference(&b).ranges().collect::<Vec<_>>(),
+            [1..=4, 26..=30]
+        );
+        assert_eq!(
+            b.difference(&a).ranges().collect::<Vec<_>>(),
+            [11..=19]
+        );
+    }
+
+    #[test]
+    fn test_whole_domain() {
+        let all = IntervalSet::from_iter([0..=u64::MAX]);
+        assert_eq!(all.covered_len(), 1 << 64);
+        assert!(all.contains(u64::MAX));
+        let ends = IntervalSet::from_iter([
+            0..=0,
+            u64::MAX..=u64::MAX,
+        ]);
+        assert_eq!(
+            all.difference(&ends)
+                .ranges()
+                .collect::<Vec<_>>(),
+            [1..=u64::MAX - 1]
+        );
+        assert_eq!(
+            ends.gaps().collect::<Vec<_>>(),
+            [1..=u64::MAX - 1]
+        );
+        let mut signed = IntervalSet::new();
+        signed.extend([i8::MIN..=-1, 0..=i8::MAX]);
+        assert_eq!(signed.len(), 1);
+        assert_eq!(signed.covered_len(), 256);
+    }
+
+    fn ranges() -> impl Strategy<Value = Vec<(u8, u8)>> {
+        prop::collection::vec(
+            (any::<u8>(), 0..40u8).prop_map(
+                |(start, len)| {
+                    (start, start.saturating_add(len))
+                },
+            ),
+            0..12,
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_bits(ranges in ranges()) {
+            let bits = bits(&ranges);
+            let set = set(&ranges);
+            prop_assert_eq!(&set, &from_bits(&bits));
+            let mut inserted = IntervalSet::new();
+            inserted.extend(ranges.iter().map(|&(start, end)| start..=end));
+            prop_assert_eq!(&inserted, &set);
+            for value in 0..=u8::MAX {
+                prop_assert_eq!(set.contains(value), bits[usize::from(value)]);
+            }
+            prop_assert_eq!(
+                set.covered_len(),
+                bits.iter().filter(|&&bit| bit).count() as u128
+            );
+        }
+
+        #[test]
+        fn prop_gaps(ranges in ranges()) {
+            let set = set(&ranges);
+            let bits = bits(&ranges);
+            let gaps = set.gaps().collect::<Vec<_>>();
+            prop_assert_eq!(gaps.len(), set.len().saturating_sub(1));
+            let gap_bits = bits_of(&IntervalSet::from_iter(gaps));
+            let (first, last) = match (bits.iter().position(|&bit| bit), bits.iter().rposition(|&bit| bit)) {
+                (Some(first), Some(last)) => (first, last),
+                _ => (1, 0),
+            };
+            for value in 0..256 {
+                let in_gap = (first..=last).contains(&value) && !bits[value];
+                prop_assert_eq!(gap_bits[value], in_gap);
+            }
+        }
+
+        #[test]
+        fn prop_set_operations(a in ranges(), b in ranges()) {
+            let (a_bits, b_bits) = (bits(&a), bits(&b));
+            let (a, b) = (set(&a), set(&b));
+            let combine = |op: fn(bool, bool) -> bool| {
+                let mut bits = [false; 256];
+                for value in 0..256 {
+                    bits[value] = op(a_bits[value], b_bits[value]);
+                }
+                from_bits(&bits)
+            };
+            prop_assert_eq!(a.union(&b), combine(|a, b| a || b));
+            prop_assert_eq!(a.intersection(&b), combine(|a, b| a && b));
+            prop_assert_eq!(a.difference(&b), combine(|a, b| a && !b));
+        }
+    }
+}
@@ -0,0 +1,8 @@
+pub use crate::{
+    disjoint_set::DisjointSet,
+    interval_set::{Integer, IntervalSet},
+};
+
+mod disjoint_set;
+mod interval_set;
+pub mod spatial;
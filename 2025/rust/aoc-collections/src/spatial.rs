@@ -0,0 +1,357 @@
+//! Nearest neighbour queries over integer points.
+
+use std::collections::BinaryHeap;
+
+/// A point in `D` dimensions.
+pub type Point<const D: usize> = [i64; D];
+
+pub type KdTree2 = KdTree<2>;
+pub type KdTree3 = KdTree<3>;
+
+/// A point found by a query, ordered by distance
+/// and then by index.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord,
+)]
+pub struct Neighbour {
+    pub distance_squared: i64,
+    /// the position of the point in the input of
+    /// [`KdTree::new`]
+    pub index: usize,
+}
+
+/// The squared euclidean distance, which keeps
+/// the ordering of distances without leaving the
+/// integers.
+pub fn distance_squared<const D: usize>(
+    a: &Point<D>,
+    b: &Point<D>,
+) -> i64 {
+    a.iter().zip(b).map(|(a, b)| (a - b).pow(2)).sum()
+}
+
+/// A k-d tree, splitting on one axis after the
+/// other at the median point.
+///
+/// The tree is implicit: every subtree is a
+/// slice of `nodes` with its root in the middle,
+/// the smaller coordinates to its left and the
+/// larger ones to its right.
+///
+/// Distances are squared `i64`s, so coordinates
+/// should stay well within `±10^9`.
+#[derive(Clone, Debug)]
+pub struct KdTree<const D: usize> {
+    nodes: Vec<(Point<D>, usize)>,
+}
+
+impl<const D: usize> KdTree<D> {
+    pub fn new(
+        points: impl IntoIterator<Item = Point<D>>,
+    ) -> Self {
+        let mut nodes = points
+            .into_iter()
+            .enumerate()
+            .map(|(index, point)| (point, index))
+            .collect::<Vec<_>>();
+        build(&mut nodes, 0);
+        KdTree { nodes }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The point closest to `query`, which is
+    /// `query` itself when it is in the tree.
+    pub fn nearest(
+        &self,
+        query: &Point<D>,
+    ) -> Option<Neighbour> {
+        self.k_nearest(query, 1).pop()
+    }
+
+    /// The `k` points closest to `query`, closest
+    /// first.
+    pub fn k_nearest(
+        &self,
+        query: &Point<D>,
+        k: usize,
+    ) -> Vec<Neighbour> {
+        let mut best = BinaryHeap::with_capacity(
+            k.min(self.len()) + 1,
+        );
+        if k > 0 {
+            Self::search(
+                &self.nodes,
+                0,
+                query,
+                k,
+                &mut best,
+            );
+        }
+        best.into_sorted_vec()
+    }
+
+    /// Every point at most `radius` away from
+    /// `query`, closest first.
+    pub fn within(
+        &self,
+        query: &Point<D>,
+        radius: i64,
+    ) -> Vec<Neighbour> {
+        let mut found = Vec::new();
+        Self::collect_within(
+            &self.nodes,
+            0,
+            query,
+            radius.pow(2),
+            &mut found,
+        );
+        found.sort_unstable();
+        found
+    }
+
+    fn search(
+        nodes: &[(Point<D>, usize)],
+        axis: usize,
+        query: &Point<D>,
+        k: usize,
+        best: &mut BinaryHeap<Neighbour>,
+    ) {
+        if nodes.is_empty() {
+            return;
+        }
+        let middle = nodes.len() / 2;
+        let (point, index) = nodes[middle];
+        best.push(Neighbour {
+            distance_squared: distance_squared(
+                &point, query,
+            ),
+            index,
+        });
+        if best.len() > k {
+            best.pop();
+        }
+
+        let offset = query[axis] - point[axis];
+        let (near, far) = if offset < 0 {
+            (&nodes[..middle], &nodes[middle + 1..])
+        } else {
+            (&nodes[middle + 1..], &nodes[..middle])
+        };
+        let next = (axis + 1) % D;
+        Self::search(near, next, query, k, best);
+        // the far side can only hold something
+        // closer when the splitting plane is no
+        // further away than the worst found so far
+        let worst = best.peek().map_or(i64::MAX, |worst| {
+            worst.distance_squared
+        });
+        if best.len() < k || offset.pow(2) <= worst {
+            Self::search(far, next, query, k, best);
+        }
+    }
+
+    fn collect_within(
+        nodes: &[(Point<D>, usize)],
+        axis: usize,
+        query: &Point<D>,
+        radius_squared: i64,
+        found: &mut Vec<Neighbour>,
+    ) {
+        if nodes.is_empty() {
+            return;
+        }
+        let middle = nodes.len() / 2;
+        let (point, index) = nodes[middle];
+        let distance_squared =
+            distance_squared(&point, query);
+        if distance_squared <= radius_squared {
+            found.push(Neighbour {
+                distance_squared,
+                index,
+            });
+        }
+
+        let offset = query[axis] - point[axis];
+        let next = (axis + 1) % D;
+        if offset <= 0 || offset.pow(2) <= radius_squared {
+            Self::collect_within(
+                &nodes[..middle],
+                next,
+                query,
+                radius_squared,
+                found,
+            );
+        }
+        if offset >= 0 || offset.pow(2) <= radius_squared {
+            Self::collect_within(
+                &nodes[middle + 1..],
+                next,
+                query,
+                radius_squared,
+                found,
+            );
+        }
+    }
+}
+
+/// Arranges `nodes` so its middle element is the
+/// median on `axis`, then does the same for both
+/// halves on the next axis.
+fn build<const D: usize>(
+    nodes: &mut [(Point<D>, usize)],
+    axis: usize,
+) {
+    if nodes.len() <= 1 {
+        return;
+    }
+    let middle = nodes.len() / 2;
+    nodes.select_nth_unstable_by_key(middle, |node| {
+        node.0[axis]
+    });
+    let (left, right) = nodes.split_at_mut(middle);
+    let next = (axis + 1) % D;
+    build(left, next);
+    build(&mut right[1..], next);
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    /// Deterministic pseudo random points in a
+    /// cube with sides of `size`.
+    fn random_points<const D: usize>(
+        count: usize,
+        size: i64,
+    ) -> Vec<Point<D>> {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % size as u64) as i64
+        };
+        (0..count)
+            .map(|_| std::array::from_fn(|_| next()))
+            .collect()
+    }
+
+    fn brute_force<const D: usize>(
+        points: &[Point<D>],
+        query: &Point<D>,
+    ) -> Vec<Neighbour> {
+        let mut neighbours = points
+            .iter()
+            .enumerate()
+            .map(|(index, point)| Neighbour {
+                distance_squared: distance_squared(
+                    point, query,
+                ),
+                index,
+            })
+            .collect::<Vec<_>>();
+        neighbours.sort_unstable();
+        neighbours
+    }
+
+    #[test]
+    fn test_nearest() {
+        let tree = KdTree2::new([[0, 0], [5, 5], [9, 1]]);
+        assert_eq!(tree.len(), 3);
+        assert_eq!(
+            tree.nearest(&[8, 2]),
+            Some(Neighbour {
+                distance_squared: 2,
+                index: 2
+            })
+        );
+        assert_eq!(
+            tree.nearest(&[5, 5]),
+            Some(Neighbour {
+                distance_squared: 0,
+                index: 1
+            })
+        );
+        assert_eq!(KdTree3::new([]).nearest(&[0; 3]), None);
+        assert_eq!(
+            tree.k_nearest(&[0, 0], usize::MAX).len(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_within() {
+        let tree = KdTree2::new([[0, 0], [3, 4], [-3, 0]]);
+        assert_eq!(
+            tree.within(&[0, 0], 3)
+                .iter()
+                .map(|neighbour| neighbour.index)
+                .collect::<Vec<_>>(),
+            [0, 2]
+        );
+        assert_eq!(tree.within(&[0, 0], 5).len(), 3);
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(10)]
+    #[case(1000)]
+    fn test_k_nearest_matches_brute_force(
+        #[case] k: usize,
+    ) {
+        let points = random_points::<3>(500, 1000);
+        let tree = KdTree3::new(points.iter().copied());
+        for query in random_points::<3>(20, 1200) {
+            let expected = brute_force(&points, &query);
+            assert_eq!(
+                tree.k_nearest(&query, k),
+                expected[..k.min(points.len())]
+            );
+        }
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(50)]
+    #[case(400)]
+    fn test_within_matches_brute_force(
+        #[case] radius: i64,
+    ) {
+        let points = random_points::<2>(500, 1000);
+        let tree = KdTree2::new(points.iter().copied());
+        for query in random_points::<2>(20, 1200) {
+            let expected = brute_force(&points, &query)
+                .into_iter()
+                .filter(|neighbour| {
+                    neighbour.distance_squared
+                        <= radius.pow(2)
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                tree.within(&query, radius),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_duplicates() {
+        let tree = KdTree3::new([[1, 1, 1]; 4]);
+        assert_eq!(
+            tree.k_nearest(&[1, 1, 1], 4)
+                .iter()
+                .map(|neighbour| neighbour.index)
+                .collect::<Vec<_>>(),
+            [0, 1, 2, 3]
+        );
+    }
+}
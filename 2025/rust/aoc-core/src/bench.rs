@@ -0,0 +1,90 @@
+/// Generates the divan benchmarks of a day's
+/// [`Solution`](crate::Solution) on its puzzle
+/// input, for use as the whole of a day's
+/// `benches/benchmarks.rs`:
+///
+/// ```ignore
+/// aoc_core::benches!(day_01::Day);
+/// ```
+///
+/// `part1` and `part2` time parsing and solving
+/// together, like the runner does. `parse` times
+/// the parsing on its own, and `solve::part1` and
+/// `solve::part2` time each part on an already
+/// parsed input.
+#[macro_export]
+macro_rules! benches {
+    ($day:path) => {
+        fn main() {
+            // Run registered benchmarks.
+            divan::main();
+        }
+
+        fn input() -> String {
+            $crate::input::load_from_env(env!(
+                "CARGO_MANIFEST_DIR"
+            ))
+            .unwrap()
+        }
+
+        #[divan::bench]
+        fn part1(bencher: divan::Bencher) {
+            use $crate::Solution;
+            let input = input();
+            bencher.bench(|| {
+                let parsed =
+                    <$day>::parse(divan::black_box(&input))
+                        .unwrap();
+                <$day>::part1(&parsed).unwrap()
+            });
+        }
+
+        #[divan::bench]
+        fn part2(bencher: divan::Bencher) {
+            use $crate::Solution;
+            let input = input();
+            bencher.bench(|| {
+                let parsed =
+                    <$day>::parse(divan::black_box(&input))
+                        .unwrap();
+                <$day>::part2(&parsed).unwrap()
+            });
+        }
+
+        /// Parsing the input on its own.
+        #[divan::bench]
+        fn parse(bencher: divan::Bencher) {
+            use $crate::Solution;
+            let input = input();
+            bencher.bench(|| {
+                <$day>::parse(divan::black_box(&input))
+                    .unwrap()
+            });
+        }
+
+        /// Each part on an already parsed input.
+        mod solve {
+            use $crate::Solution;
+
+            #[divan::bench]
+            fn part1(bencher: divan::Bencher) {
+                let input = super::input();
+                let parsed = <$day>::parse(&input).unwrap();
+                bencher.bench(|| {
+                    <$day>::part1(divan::black_box(&parsed))
+                        .unwrap()
+                });
+            }
+
+            #[divan::bench]
+            fn part2(bencher: divan::Bencher) {
+                let input = super::input();
+                let parsed = <$day>::parse(&input).unwrap();
+                bencher.bench(|| {
+                    <$day>::part2(divan::black_box(&parsed))
+                        .unwrap()
+                });
+            }
+        }
+    };
+}
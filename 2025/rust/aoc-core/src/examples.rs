@@ -0,0 +1,113 @@
+use std::path::{Path, PathBuf};
+
+/// An example from the puzzle text, stored as
+/// `examples/partN-k.txt` with the expected
+/// answer next to it in
+/// `examples/partN-k.expected`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Example {
+    pub input: String,
+    pub expected: String,
+}
+
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+#[error("could not read example `{}`", path.display())]
+#[diagnostic(
+    code(aoc::examples::read),
+    help("`aoc examples <day>` writes both files")
+)]
+pub struct ExampleError {
+    path: PathBuf,
+    #[source]
+    source: std::io::Error,
+}
+
+impl Example {
+    /// Reads the example at `path` and its
+    /// `.expected` sibling.
+    pub fn read(
+        path: &Path,
+    ) -> Result<Example, ExampleError> {
+        let read = |path: PathBuf| {
+            std::fs::read_to_string(&path).map_err(
+                |source| ExampleError { path, source },
+            )
+        };
+        Ok(Example {
+            input: read(path.to_path_buf())?,
+            expected: read(
+                path.with_extension("expected"),
+            )?
+            .trim()
+            .to_string(),
+        })
+    }
+}
+
+/// Generates an rstest `test_examples` that runs
+/// `process` on every `examples/partN-*.txt` of
+/// the crate, for use inside a part's tests
+/// module:
+///
+/// ```ignore
+/// #[cfg(test)]
+/// mod tests {
+///     use super::*;
+///
+///     aoc_core::example_tests!(part1);
+/// }
+/// ```
+#[macro_export]
+macro_rules! example_tests {
+    (part1) => {
+        $crate::example_tests!(@files "examples/part1-*.txt");
+    };
+    (part2) => {
+        $crate::example_tests!(@files "examples/part2-*.txt");
+    };
+    (@files $glob:tt) => {
+        #[rstest::rstest]
+        fn test_examples(
+            #[files($glob)] path: std::path::PathBuf,
+        ) -> miette::Result<()> {
+            let example =
+                $crate::examples::Example::read(&path)?;
+            assert_eq!(example.expected, process(&example.input)?);
+            Ok(())
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() -> miette::Result<()> {
+        let dir = std::env::temp_dir().join(format!(
+            "aoc-examples-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("part1-1.txt");
+        std::fs::write(&path, "1\n2\n").unwrap();
+        let missing = Example::read(&path);
+        std::fs::write(
+            path.with_extension("expected"),
+            "3\n",
+        )
+        .unwrap();
+        let example = Example::read(&path);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(missing.is_err());
+        assert_eq!(
+            Example {
+                input: "1\n2\n".to_string(),
+                expected: "3".to_string()
+            },
+            example?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,212 @@
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+/// Environment variable pointing at a directory
+/// that mirrors the workspace layout, e.g.
+/// `$AOC_INPUT_DIR/day-07/input.txt`.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum InputError {
+    #[error("puzzle input `{}` does not exist", path.display())]
+    #[diagnostic(
+        code(aoc::input::missing),
+        help(
+            "download it with `just get-input {day}`, set \
+             `AOC_INPUT_DIR`, or pass the path explicitly \
+             (`-` reads from stdin)"
+        )
+    )]
+    Missing { path: PathBuf, day: String },
+    #[error("could not read puzzle input `{}`", path.display())]
+    #[diagnostic(code(aoc::input::read))]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("could not read puzzle input from stdin")]
+    #[diagnostic(code(aoc::input::stdin))]
+    Stdin(#[source] std::io::Error),
+}
+
+/// Where a puzzle input is read from.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Source {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Source {
+    /// Picks the input shared by both parts of
+    /// the day crate in `day_dir`, in order of
+    /// precedence:
+    ///
+    /// 1. `arg`, where `-` means stdin
+    /// 2. `input_dir/day-NN/input.txt`
+    /// 3. `day_dir/input.txt`
+    pub fn resolve(
+        arg: Option<&str>,
+        input_dir: Option<&Path>,
+        day_dir: &Path,
+    ) -> Source {
+        let filename = "input.txt";
+        match (arg, input_dir) {
+            (Some(arg), _) => Source::from_arg(arg),
+            (None, Some(input_dir)) => Source::File(
+                input_dir
+                    .join(
+                        day_dir
+                            .file_name()
+                            .unwrap_or_default(),
+                    )
+                    .join(filename),
+            ),
+            (None, None) => {
+                Source::File(day_dir.join(filename))
+            }
+        }
+    }
+
+    /// An explicitly requested input, where `-`
+    /// means stdin.
+    pub fn from_arg(arg: &str) -> Source {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::File(path.into()),
+        }
+    }
+
+    /// Like [`Source::resolve`], reading the
+    /// input directory from
+    /// [`INPUT_DIR_VAR`].
+    pub fn from_env(
+        arg: Option<&str>,
+        day_dir: &Path,
+    ) -> Source {
+        let input_dir = std::env::var_os(INPUT_DIR_VAR);
+        Source::resolve(
+            arg,
+            input_dir.as_deref().map(Path::new),
+            day_dir,
+        )
+    }
+
+    pub fn read(&self) -> Result<String, InputError> {
+        match self {
+            Source::Stdin => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(InputError::Stdin)?;
+                Ok(input)
+            }
+            Source::File(path) if !path.exists() => {
+                Err(InputError::Missing {
+                    path: path.clone(),
+                    day: day_name(path),
+                })
+            }
+            Source::File(path) => std::fs::read_to_string(
+                path,
+            )
+            .map_err(|source| InputError::Read {
+                path: path.clone(),
+                source,
+            }),
+        }
+    }
+}
+
+/// Name of the `day-NN` directory a path lives
+/// in, used to suggest the right `just` recipe.
+fn day_name(path: &Path) -> String {
+    path.ancestors()
+        .filter_map(|dir| dir.file_name()?.to_str())
+        .find(|name| name.starts_with("day-"))
+        .unwrap_or("day-01")
+        .to_string()
+}
+
+/// Loads the input for a `src/bin/partN.rs`,
+/// honouring the first command line argument.
+///
+/// `day_dir` is the crate's
+/// `env!("CARGO_MANIFEST_DIR")`.
+pub fn load(day_dir: &str) -> Result<String, InputError> {
+    let arg = std::env::args().nth(1);
+    Source::from_env(arg.as_deref(), Path::new(day_dir))
+        .read()
+}
+
+/// Loads the input for a benchmark, which can't
+/// use the command line since divan owns it.
+pub fn load_from_env(
+    day_dir: &str,
+) -> Result<String, InputError> {
+    Source::from_env(None, Path::new(day_dir)).read()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(Some("-"), None, Source::Stdin)]
+    #[case(Some("-"), Some("inputs"), Source::Stdin)]
+    #[case(
+        Some("other.txt"),
+        Some("inputs"),
+        Source::File("other.txt".into())
+    )]
+    #[case(
+        None,
+        Some("inputs"),
+        Source::File("inputs/day-07/input.txt".into())
+    )]
+    #[case(
+        None,
+        None,
+        Source::File("aoc/day-07/input.txt".into())
+    )]
+    fn test_resolve(
+        #[case] arg: Option<&str>,
+        #[case] input_dir: Option<&str>,
+        #[case] expected: Source,
+    ) {
+        assert_eq!(
+            Source::resolve(
+                arg,
+                input_dir.map(Path::new),
+                Path::new("aoc/day-07"),
+            ),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_read_missing() {
+        let error = Source::File(
+            "aoc/day-07/does-not-exist.txt".into(),
+        )
+        .read()
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            InputError::Missing { day, .. } if day == "day-07"
+        ));
+    }
+
+    #[test]
+    fn test_read_file() -> miette::Result<()> {
+        let path = std::env::temp_dir()
+            .join("aoc-core-test-read-file.txt");
+        std::fs::write(&path, "1,2,3").unwrap();
+        assert_eq!(Source::File(path).read()?, "1,2,3");
+        Ok(())
+    }
+}
@@ -0,0 +1,191 @@
+use std::time::{Duration, Instant};
+
+use miette::miette;
+
+mod bench;
+pub mod examples;
+pub mod input;
+pub mod parse;
+
+/// A single day's puzzle.
+///
+/// Both parts receive the output of [`parse`],
+/// so the input is only parsed once and the
+/// parsing time can be measured on its own.
+/// Days without a dedicated parse step use
+/// `type Parsed<'a> = &'a str` and hand the input
+/// straight through.
+///
+/// [`parse`]: Solution::parse
+pub trait Solution {
+    const YEAR: u16;
+    const DAY: u8;
+    const TITLE: &'static str;
+
+    type Parsed<'a>;
+
+    fn parse(
+        input: &str,
+    ) -> miette::Result<Self::Parsed<'_>>;
+
+    fn part1(
+        parsed: &Self::Parsed<'_>,
+    ) -> miette::Result<String>;
+
+    fn part2(
+        parsed: &Self::Parsed<'_>,
+    ) -> miette::Result<String>;
+}
+
+/// The answer of a single part together with the
+/// time spent parsing and solving.
+#[derive(Debug)]
+pub struct Outcome {
+    pub answer: String,
+    pub parse: Duration,
+    pub solve: Duration,
+}
+
+impl Outcome {
+    pub fn total(&self) -> Duration {
+        self.parse + self.solve
+    }
+}
+
+/// Type-erased handle to a [`Solution`], so that
+/// tooling can keep every day in a single list.
+#[derive(Clone, Copy)]
+pub struct Descriptor {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'static str,
+    run: fn(&str, u8) -> miette::Result<Outcome>,
+}
+
+impl Descriptor {
+    pub const fn of<S: Solution>() -> Self {
+        Descriptor {
+            year: S::YEAR,
+            day: S::DAY,
+            title: S::TITLE,
+            run: run::<S>,
+        }
+    }
+
+    /// Parses `input` and solves `part` (1 or 2)
+    /// with it.
+    pub fn run(
+        &self,
+        input: &str,
+        part: u8,
+    ) -> miette::Result<Outcome> {
+        (self.run)(input, part)
+    }
+}
+
+impl std::fmt::Debug for Descriptor {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("Descriptor")
+            .field("year", &self.year)
+            .field("day", &self.day)
+            .field("title", &self.title)
+            .finish_non_exhaustive()
+    }
+}
+
+fn run<S: Solution>(
+    input: &str,
+    part: u8,
+) -> miette::Result<Outcome> {
+    let solve = match part {
+        1 => S::part1,
+        2 => S::part2,
+        other => {
+            return Err(miette!(
+                "part must be 1 or 2, got {other}"
+            ));
+        }
+    };
+
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse = start.elapsed();
+
+    let start = Instant::now();
+    let answer = solve(&parsed)?;
+    let solve = start.elapsed();
+
+    Ok(Outcome {
+        answer,
+        parse,
+        solve,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const YEAR: u16 = 2025;
+        const DAY: u8 = 42;
+        const TITLE: &'static str = "Sum";
+
+        type Parsed<'a> = Vec<u32>;
+
+        fn parse(
+            input: &str,
+        ) -> miette::Result<Self::Parsed<'_>> {
+            input
+                .split(',')
+                .map(|n| {
+                    n.parse().map_err(|_| {
+                        miette!("not a number: `{n}`")
+                    })
+                })
+                .collect()
+        }
+
+        fn part1(
+            numbers: &Self::Parsed<'_>,
+        ) -> miette::Result<String> {
+            Ok(numbers.iter().sum::<u32>().to_string())
+        }
+
+        fn part2(
+            numbers: &Self::Parsed<'_>,
+        ) -> miette::Result<String> {
+            Ok(numbers.iter().product::<u32>().to_string())
+        }
+    }
+
+    const SUM: Descriptor = Descriptor::of::<Sum>();
+
+    #[rstest]
+    #[case(1, "9")]
+    #[case(2, "24")]
+    fn test_descriptor_run(
+        #[case] part: u8,
+        #[case] expected: &str,
+    ) -> miette::Result<()> {
+        assert_eq!(SUM.day, 42);
+        assert_eq!(
+            expected,
+            SUM.run("2,3,4", part)?.answer
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_descriptor_run_errors() {
+        assert!(SUM.run("2,3,4", 3).is_err());
+        assert!(SUM.run("2,x,4", 1).is_err());
+    }
+}
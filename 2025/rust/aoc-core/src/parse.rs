@@ -0,0 +1,593 @@
+//! nom parsers for the usual shapes of puzzle
+//! input, whose failures point at the offending
+//! line and column.
+//!
+//! ```
+//! use aoc_core::parse::{comma_list, finish, range};
+//!
+//! let ranges = finish("11-22,95-115", comma_list(range))?;
+//! assert_eq!(ranges, [11..=22, 95..=115]);
+//!
+//! let error = finish("11-22,95-x", comma_list(range))
+//!     .unwrap_err();
+//! assert_eq!(
+//!     error.to_string(),
+//!     "expected an unsigned integer at line 1, column 10"
+//! );
+//! # Ok::<(), aoc_core::parse::ParseError>(())
+//! ```
+
+use std::{fmt, ops::RangeInclusive, str::FromStr};
+
+use miette::SourceSpan;
+use nom::{
+    Input, Parser,
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{
+        char, digit1, line_ending, multispace0, one_of,
+    },
+    combinator::{
+        all_consuming, consumed, cut, eof, map_res, not,
+        opt, peek, recognize,
+    },
+    error::{
+        ContextError, ErrorKind, FromExternalError, context,
+    },
+    multi::{many1, separated_list1},
+    sequence::{pair, separated_pair, terminated},
+};
+use nom_locate::LocatedSpan;
+
+/// Puzzle input that knows its position.
+pub type Span<'a> = LocatedSpan<&'a str>;
+
+pub type IResult<'a, O> =
+    nom::IResult<Span<'a>, O, SpanError<'a>>;
+
+/// A nom parser over a [`Span`] failing with a
+/// [`SpanError`], as taken and returned by the
+/// combinators in this module.
+pub trait SpanParser<'a, O>:
+    Parser<Span<'a>, Output = O, Error = SpanError<'a>>
+{
+}
+
+impl<'a, O, P> SpanParser<'a, O> for P where
+    P: Parser<Span<'a>, Output = O, Error = SpanError<'a>>
+{
+}
+
+/// What a parser was looking for when it failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Expected {
+    Char(char),
+    Kind(ErrorKind),
+}
+
+/// The error of the parsers in this module, see
+/// [`finish`] to turn it into a [`ParseError`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpanError<'a> {
+    span: Span<'a>,
+    expected: Expected,
+    /// from the innermost to the outermost
+    contexts: Vec<&'static str>,
+    /// why a parsed value was rejected, e.g. a
+    /// number that doesn't fit
+    cause: Option<String>,
+}
+
+impl<'a> nom::error::ParseError<Span<'a>>
+    for SpanError<'a>
+{
+    fn from_error_kind(
+        span: Span<'a>,
+        kind: ErrorKind,
+    ) -> Self {
+        SpanError {
+            span,
+            expected: Expected::Kind(kind),
+            contexts: Vec::new(),
+            cause: None,
+        }
+    }
+
+    fn append(
+        _span: Span<'a>,
+        _kind: ErrorKind,
+        other: Self,
+    ) -> Self {
+        other
+    }
+
+    fn from_char(span: Span<'a>, expected: char) -> Self {
+        SpanError {
+            expected: Expected::Char(expected),
+            ..Self::from_error_kind(span, ErrorKind::Char)
+        }
+    }
+}
+
+impl<'a> ContextError<Span<'a>> for SpanError<'a> {
+    fn add_context(
+        _span: Span<'a>,
+        context: &'static str,
+        mut other: Self,
+    ) -> Self {
+        other.contexts.push(context);
+        other
+    }
+}
+
+impl<'a, E: fmt::Display> FromExternalError<Span<'a>, E>
+    for SpanError<'a>
+{
+    fn from_external_error(
+        span: Span<'a>,
+        kind: ErrorKind,
+        error: E,
+    ) -> Self {
+        SpanError {
+            cause: Some(error.to_string()),
+            ..nom::error::ParseError::from_error_kind(
+                span, kind,
+            )
+        }
+    }
+}
+
+/// Input that doesn't have the expected shape.
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+#[error(
+    "expected {expected} at line {line}, column {column}"
+)]
+#[diagnostic(code(aoc::parse))]
+pub struct ParseError {
+    #[source_code]
+    input: String,
+    #[label("expected {expected}")]
+    span: SourceSpan,
+    expected: String,
+    line: u32,
+    column: usize,
+    #[help]
+    help: Option<String>,
+}
+
+impl ParseError {
+    fn new(input: &str, error: SpanError<'_>) -> Self {
+        let mut contexts = error.contexts.iter();
+        let expected = match error.expected {
+            Expected::Char(ch) => format!("`{ch}`"),
+            Expected::Kind(kind) => match contexts.next() {
+                Some(context) => context.to_string(),
+                None => describe(kind),
+            },
+        };
+        let within = contexts
+            .map(|context| {
+                format!("while parsing {context}")
+            })
+            .collect::<Vec<_>>();
+        let help =
+            error.cause.into_iter().chain(within).reduce(
+                |help, context| {
+                    format!("{help}, {context}")
+                },
+            );
+
+        ParseError {
+            input: input.to_string(),
+            span: (
+                error.span.location_offset(),
+                token_len(error.span.fragment()),
+            )
+                .into(),
+            expected,
+            line: error.span.location_line(),
+            column: error.span.get_utf8_column(),
+            help,
+        }
+    }
+}
+
+fn describe(kind: ErrorKind) -> String {
+    match kind {
+        ErrorKind::Eof => {
+            "the end of the input".to_string()
+        }
+        ErrorKind::Digit => "a digit".to_string(),
+        ErrorKind::CrLf => "a line ending".to_string(),
+        kind => kind.description().to_lowercase(),
+    }
+}
+
+/// The length of the word or symbol `rest`
+/// starts with, to underline in the diagnostic.
+fn token_len(rest: &str) -> usize {
+    match rest
+        .find(|ch: char| !ch.is_alphanumeric())
+        .unwrap_or(rest.len())
+    {
+        0 => rest.chars().next().map_or(0, char::len_utf8),
+        len => len,
+    }
+}
+
+/// Runs `parser` over the whole of `input`,
+/// ignoring trailing whitespace.
+pub fn finish<'a, O>(
+    input: &'a str,
+    parser: impl SpanParser<'a, O>,
+) -> Result<O, ParseError> {
+    let span = Span::new(input.trim_end());
+    match all_consuming(parser).parse(span) {
+        Ok((_rest, output)) => Ok(output),
+        Err(nom::Err::Error(error))
+        | Err(nom::Err::Failure(error)) => {
+            Err(ParseError::new(input, error))
+        }
+        // complete parsers never ask for more
+        Err(nom::Err::Incomplete(_)) => {
+            Err(ParseError::new(
+                input,
+                nom::error::ParseError::from_error_kind(
+                    span.take_from(span.input_len()),
+                    ErrorKind::Complete,
+                ),
+            ))
+        }
+    }
+}
+
+/// An integer without a sign, e.g. `42`.
+pub fn unsigned<'a, T>(input: Span<'a>) -> IResult<'a, T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    context(
+        "an unsigned integer",
+        map_res(digit1, |digits: Span<'a>| {
+            digits.fragment().parse::<T>()
+        }),
+    )
+    .parse(input)
+}
+
+/// An integer with an optional sign, e.g. `-42`.
+pub fn signed<'a, T>(input: Span<'a>) -> IResult<'a, T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    context(
+        "an integer",
+        map_res(
+            recognize(pair(opt(one_of("+-")), digit1)),
+            |digits: Span<'a>| {
+                digits.fragment().parse::<T>()
+            },
+        ),
+    )
+    .parse(input)
+}
+
+/// An inclusive range like `3-5`.
+pub fn range(
+    input: Span<'_>,
+) -> IResult<'_, RangeInclusive<u64>> {
+    context(
+        "a range like `3-5`",
+        separated_pair(unsigned, char('-'), unsigned),
+    )
+    .map(|(start, end)| start..=end)
+    .parse(input)
+}
+
+/// One or more `item`s separated by commas, which
+/// may be followed by whitespace.
+pub fn comma_list<'a, O>(
+    item: impl SpanParser<'a, O>,
+) -> impl SpanParser<'a, Vec<O>> {
+    separated_list1((char(','), multispace0), cut(item))
+}
+
+/// One `item` per line, each taking up the whole
+/// line. Stops at a blank line, see [`sections`].
+pub fn lines<'a, O>(
+    item: impl SpanParser<'a, O>,
+) -> impl SpanParser<'a, Vec<O>> {
+    separated_list1(
+        terminated(line_ending, not(line_ending)),
+        cut(terminated(item, end_of_line)),
+    )
+}
+
+/// Blocks of input separated by blank lines.
+pub fn sections<'a, O>(
+    section: impl SpanParser<'a, O>,
+) -> impl SpanParser<'a, Vec<O>> {
+    separated_list1(
+        (line_ending, line_ending),
+        cut(section),
+    )
+}
+
+/// Exactly two blocks separated by a blank line.
+pub fn two_sections<'a, A, B>(
+    first: impl SpanParser<'a, A>,
+    second: impl SpanParser<'a, B>,
+) -> impl SpanParser<'a, (A, B)> {
+    separated_pair(
+        first,
+        context(
+            "a blank line",
+            (line_ending, line_ending),
+        ),
+        cut(second),
+    )
+}
+
+/// Rows of `cell`s, one per line, all as long as
+/// the first.
+pub fn grid<'a, T>(
+    cell: impl SpanParser<'a, T>,
+) -> impl SpanParser<'a, Vec<Vec<T>>> {
+    let mut rows = lines(consumed(many1(cell)));
+    move |input: Span<'a>| -> IResult<'a, Vec<Vec<T>>> {
+        let (rest, rows) = rows.parse(input)?;
+        let width = rows[0].1.len();
+        if let Some((row, _)) = rows
+            .iter()
+            .find(|(_, cells)| cells.len() != width)
+        {
+            return Err(nom::Err::Failure(SpanError {
+                contexts: vec![
+                    "a row as long as the first",
+                ],
+                ..nom::error::ParseError::from_error_kind(
+                    *row,
+                    ErrorKind::Verify,
+                )
+            }));
+        }
+        Ok((
+            rest,
+            rows.into_iter()
+                .map(|(_, cells)| cells)
+                .collect(),
+        ))
+    }
+}
+
+/// A grid of the given characters, e.g.
+/// `char_grid(".#")`.
+pub fn char_grid<'a>(
+    cells: &'static str,
+) -> impl SpanParser<'a, Vec<Vec<char>>> {
+    grid(one_of(cells))
+}
+
+/// Succeeds without consuming anything at the end
+/// of a line or of the input.
+fn end_of_line(input: Span<'_>) -> IResult<'_, ()> {
+    context(
+        "the end of the line",
+        peek(alt((tag("\n"), tag("\r\n"), eof))),
+    )
+    .map(|_| ())
+    .parse(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn error<O: fmt::Debug>(
+        result: Result<O, ParseError>,
+    ) -> (u32, usize, String, (usize, usize)) {
+        let error = result.unwrap_err();
+        (
+            error.line,
+            error.column,
+            error.expected,
+            (error.span.offset(), error.span.len()),
+        )
+    }
+
+    #[rstest]
+    #[case("0", 0)]
+    #[case("18446744073709551615", u64::MAX)]
+    fn test_unsigned(
+        #[case] input: &str,
+        #[case] expected: u64,
+    ) {
+        assert_eq!(
+            finish(input, unsigned::<u64>).unwrap(),
+            expected
+        );
+    }
+
+    #[rstest]
+    #[case("-12", -12)]
+    #[case("+7", 7)]
+    #[case("7", 7)]
+    fn test_signed(
+        #[case] input: &str,
+        #[case] expected: i64,
+    ) {
+        assert_eq!(
+            finish(input, signed::<i64>).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_number_too_large() {
+        let result = finish("256", unsigned::<u8>);
+        assert_eq!(
+            result.as_ref().unwrap_err().help.as_deref(),
+            Some("number too large to fit in target type")
+        );
+        assert_eq!(
+            error(result),
+            (
+                1,
+                1,
+                "an unsigned integer".to_string(),
+                (0, 3)
+            )
+        );
+    }
+
+    #[test]
+    fn test_comma_list() {
+        assert_eq!(
+            finish("1-2, 3-3,\n4-6\n", comma_list(range))
+                .unwrap(),
+            [1..=2, 3..=3, 4..=6]
+        );
+        assert_eq!(
+            error(finish("1-2,3", comma_list(range))),
+            (1, 6, "`-`".to_string(), (5, 0))
+        );
+        assert_eq!(
+            error(finish("1-2,,3-4", comma_list(range))),
+            (
+                1,
+                5,
+                "an unsigned integer".to_string(),
+                (4, 1)
+            )
+        );
+    }
+
+    #[test]
+    fn test_lines() {
+        assert_eq!(
+            finish(
+                "7,1\n11,1\n",
+                lines(separated_pair(
+                    unsigned::<u32>,
+                    char(','),
+                    unsigned::<u32>
+                ))
+            )
+            .unwrap(),
+            [(7, 1), (11, 1)]
+        );
+        let tiles = || {
+            lines(separated_pair(
+                unsigned::<u32>,
+                char(','),
+                unsigned::<u32>,
+            ))
+        };
+        assert_eq!(
+            error(finish("7,1\n11,x1", tiles())),
+            (
+                2,
+                4,
+                "an unsigned integer".to_string(),
+                (7, 2)
+            )
+        );
+        assert_eq!(
+            error(finish("7,1\n11,1 2", tiles())),
+            (
+                2,
+                5,
+                "the end of the line".to_string(),
+                (8, 1)
+            )
+        );
+    }
+
+    #[test]
+    fn test_sections() {
+        assert_eq!(
+            finish(
+                "1\n2\n\n3\n",
+                sections(lines(unsigned::<u8>))
+            )
+            .unwrap(),
+            [vec![1, 2], vec![3]]
+        );
+        assert_eq!(
+            finish(
+                "3-5\n10-14\n\n1\n5\n",
+                two_sections(
+                    lines(range),
+                    lines(unsigned::<u64>)
+                )
+            )
+            .unwrap(),
+            (vec![3..=5, 10..=14], vec![1, 5])
+        );
+        assert_eq!(
+            error(finish(
+                "3-5\n1",
+                two_sections(
+                    lines(range),
+                    lines(unsigned::<u64>)
+                )
+            )),
+            (2, 2, "`-`".to_string(), (5, 0))
+        );
+        assert_eq!(
+            error(finish(
+                "3-5",
+                two_sections(
+                    lines(range),
+                    lines(unsigned::<u64>)
+                )
+            )),
+            (1, 4, "a blank line".to_string(), (3, 0))
+        );
+    }
+
+    #[test]
+    fn test_char_grid() {
+        assert_eq!(
+            finish("@.\n.@\n", char_grid(".@")).unwrap(),
+            [['@', '.'], ['.', '@']]
+        );
+        assert_eq!(
+            error(finish("@.\n.x", char_grid(".@"))),
+            (
+                2,
+                2,
+                "the end of the line".to_string(),
+                (4, 1)
+            )
+        );
+        assert_eq!(
+            error(finish("@.\n.@@", char_grid(".@"))),
+            (
+                2,
+                1,
+                "a row as long as the first".to_string(),
+                (3, 1)
+            )
+        );
+    }
+
+    #[test]
+    fn test_diagnostic() {
+        let error =
+            finish("1-2\n3-x\n", lines(range)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected an unsigned integer at line 2, column 3"
+        );
+        assert_eq!(
+            error.help.as_deref(),
+            Some("while parsing a range like `3-5`")
+        );
+        assert_eq!(error.input, "1-2\n3-x\n");
+    }
+}
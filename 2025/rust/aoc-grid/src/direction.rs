@@ -0,0 +1,166 @@
+/// A cell in a [`Grid`](crate::Grid), with `(0,
+/// 0)` in the top left corner.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Hash,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub struct Position {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Position {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Position { x, y }
+    }
+
+    /// The position one step in `direction`, or
+    /// `None` when that would leave the first
+    /// quadrant.
+    pub fn step(
+        self,
+        direction: Direction,
+    ) -> Option<Self> {
+        let (dx, dy) = direction.offset();
+        Some(Position {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+}
+
+impl From<(usize, usize)> for Position {
+    fn from((x, y): (usize, usize)) -> Self {
+        Position { x, y }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// The 4-neighbourhood, clockwise from north.
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// The 8-neighbourhood, clockwise from north.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// `(dx, dy)` of a single step, with y
+    /// growing southwards.
+    pub const fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::NorthEast => (1, -1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (0, 1),
+            Direction::SouthWest => (-1, 1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, -1),
+        }
+    }
+
+    pub const fn opposite(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::NorthEast => Direction::SouthWest,
+            Direction::East => Direction::West,
+            Direction::SouthEast => Direction::NorthWest,
+            Direction::South => Direction::North,
+            Direction::SouthWest => Direction::NorthEast,
+            Direction::West => Direction::East,
+            Direction::NorthWest => Direction::SouthEast,
+        }
+    }
+
+    pub const fn turn_right(self) -> Direction {
+        match self {
+            Direction::North => Direction::East,
+            Direction::NorthEast => Direction::SouthEast,
+            Direction::East => Direction::South,
+            Direction::SouthEast => Direction::SouthWest,
+            Direction::South => Direction::West,
+            Direction::SouthWest => Direction::NorthWest,
+            Direction::West => Direction::North,
+            Direction::NorthWest => Direction::NorthEast,
+        }
+    }
+
+    pub const fn turn_left(self) -> Direction {
+        self.turn_right().opposite()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step() {
+        let center = Position::new(1, 1);
+        assert_eq!(
+            center.step(Direction::North),
+            Some(Position::new(1, 0))
+        );
+        assert_eq!(
+            center.step(Direction::SouthWest),
+            Some(Position::new(0, 2))
+        );
+        assert_eq!(
+            Position::new(0, 0).step(Direction::West),
+            None
+        );
+        assert_eq!(
+            Position::new(0, 0).step(Direction::North),
+            None
+        );
+    }
+
+    #[test]
+    fn test_turns() {
+        for direction in Direction::ALL {
+            assert_eq!(
+                direction.turn_right().turn_left(),
+                direction
+            );
+            assert_eq!(
+                direction.opposite().opposite(),
+                direction
+            );
+        }
+        assert_eq!(
+            Direction::North.turn_left(),
+            Direction::West
+        );
+    }
+}
@@ -0,0 +1,520 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+pub use crate::direction::{Direction, Position};
+
+mod direction;
+
+#[derive(
+    Debug,
+    PartialEq,
+    Eq,
+    thiserror::Error,
+    miette::Diagnostic,
+)]
+pub enum GridError {
+    #[error("unexpected character `{ch}` at line {}, column {}", position.y + 1, position.x + 1)]
+    #[diagnostic(code(aoc::grid::unexpected_char))]
+    UnexpectedChar { ch: char, position: Position },
+    #[error("line {} has {found} cells, expected {expected}", row + 1)]
+    #[diagnostic(
+        code(aoc::grid::ragged),
+        help(
+            "every line of a grid should be equally long"
+        )
+    )]
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+/// A rectangular grid stored row by row in a
+/// single `Vec`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Parses one row per line, mapping every
+    /// character with `cell`, which returns
+    /// `None` for characters that don't
+    /// belong in the grid.
+    pub fn parse(
+        input: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, GridError> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+        for (y, line) in input.lines().enumerate() {
+            let row_start = cells.len();
+            for (x, ch) in line.chars().enumerate() {
+                let value = cell(ch).ok_or(
+                    GridError::UnexpectedChar {
+                        ch,
+                        position: Position::new(x, y),
+                    },
+                )?;
+                cells.push(value);
+            }
+            let found = cells.len() - row_start;
+            match width {
+                None => width = Some(found),
+                Some(expected) if expected != found => {
+                    return Err(GridError::Ragged {
+                        row: y,
+                        expected,
+                        found,
+                    });
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+
+    /// Builds a grid from cells in row-major
+    /// order.
+    ///
+    /// # Panics
+    ///
+    /// When `cells` doesn't fill a whole number
+    /// of rows.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            cells.len().is_multiple_of(width),
+            "{} cells can't be split into rows of {width}",
+            cells.len()
+        );
+        let height =
+            cells.len().checked_div(width).unwrap_or(0);
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        position.x < self.width && position.y < self.height
+    }
+
+    fn offset(&self, position: Position) -> Option<usize> {
+        self.contains(position)
+            .then(|| position.y * self.width + position.x)
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.offset(position)
+            .map(|offset| &self.cells[offset])
+    }
+
+    pub fn get_mut(
+        &mut self,
+        position: Position,
+    ) -> Option<&mut T> {
+        self.offset(position)
+            .map(|offset| &mut self.cells[offset])
+    }
+
+    /// Access that wraps around the edges, as if
+    /// the grid were tiled infinitely in
+    /// every direction.
+    ///
+    /// # Panics
+    ///
+    /// When the grid is empty.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self[Position::new(x, y)]
+    }
+
+    /// The neighbour in `direction`, if it is
+    /// inside the grid.
+    pub fn neighbour(
+        &self,
+        position: Position,
+        direction: Direction,
+    ) -> Option<Position> {
+        position
+            .step(direction)
+            .filter(|&neighbour| self.contains(neighbour))
+    }
+
+    /// The up to 4 orthogonal neighbours inside
+    /// the grid, clockwise from north.
+    pub fn neighbours4(
+        &self,
+        position: Position,
+    ) -> impl Iterator<Item = Position> + '_ {
+        Direction::ORTHOGONAL.into_iter().filter_map(
+            move |direction| {
+                self.neighbour(position, direction)
+            },
+        )
+    }
+
+    /// The up to 8 surrounding neighbours inside
+    /// the grid, clockwise from north.
+    pub fn neighbours8(
+        &self,
+        position: Position,
+    ) -> impl Iterator<Item = Position> + '_ {
+        Direction::ALL.into_iter().filter_map(
+            move |direction| {
+                self.neighbour(position, direction)
+            },
+        )
+    }
+
+    /// Every position, row by row.
+    pub fn positions(
+        &self,
+    ) -> impl Iterator<Item = Position> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| {
+            (0..width).map(move |x| Position::new(x, y))
+        })
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| {
+            &self.cells
+                [y * self.width..(y + 1) * self.width]
+        })
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).filter_map(|y| self.row(y))
+    }
+
+    pub fn column(
+        &self,
+        x: usize,
+    ) -> Option<impl Iterator<Item = &T>> {
+        (x < self.width).then(|| {
+            self.cells.iter().skip(x).step_by(self.width)
+        })
+    }
+
+    pub fn columns(
+        &self,
+    ) -> impl Iterator<Item = impl Iterator<Item = &T>>
+    {
+        (0..self.width).filter_map(|x| self.column(x))
+    }
+
+    pub fn map<U>(
+        &self,
+        f: impl FnMut(&T) -> U,
+    ) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(
+        width: usize,
+        height: usize,
+        fill: T,
+    ) -> Self {
+        Grid {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal,
+    /// so rows become columns.
+    pub fn transpose(&self) -> Self {
+        self.remap(self.height, self.width, |x, y| {
+            Position::new(y, x)
+        })
+    }
+
+    /// Rotates a quarter turn clockwise.
+    pub fn rotate_clockwise(&self) -> Self {
+        let height = self.height;
+        self.remap(height, self.width, |x, y| {
+            Position::new(y, height - 1 - x)
+        })
+    }
+
+    /// Rotates a quarter turn counterclockwise.
+    pub fn rotate_counterclockwise(&self) -> Self {
+        let width = self.width;
+        self.remap(self.height, width, |x, y| {
+            Position::new(width - 1 - y, x)
+        })
+    }
+
+    /// Builds a `width` by `height` grid whose
+    /// cell at `(x, y)` is taken from
+    /// `source(x, y)`.
+    fn remap(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> Position,
+    ) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self[source(x, y)].clone())
+            .collect();
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "{position:?} is outside of the {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position).unwrap_or_else(|| {
+            panic!(
+                "{position:?} is outside of the {width}x{height} grid"
+            )
+        })
+    }
+}
+
+/// Renders one line per row, e.g. after
+/// `grid.map(|&roll| if roll { '@' } else { '.'
+/// })`.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn chars(input: &str) -> Grid<char> {
+        Grid::parse(input, Some).unwrap()
+    }
+
+    fn p(x: usize, y: usize) -> Position {
+        Position::new(x, y)
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse("@.\n.@", |ch| match ch {
+            '.' => Some(false),
+            '@' => Some(true),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(
+            grid,
+            Grid::from_vec(
+                2,
+                vec![true, false, false, true]
+            )
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let digits = |ch: char| ch.to_digit(10);
+        assert_eq!(
+            Grid::parse("12\n3x", digits),
+            Err(GridError::UnexpectedChar {
+                ch: 'x',
+                position: p(1, 1)
+            })
+        );
+        assert_eq!(
+            Grid::parse("12\n345", digits),
+            Err(GridError::Ragged {
+                row: 1,
+                expected: 2,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_get() {
+        let grid = chars("@.");
+        assert_eq!(grid.get(p(0, 0)), Some(&'@'));
+        assert_eq!(grid.get(p(1, 0)), Some(&'.'));
+        assert_eq!(grid.get(p(2, 0)), None);
+        assert_eq!(grid.get(p(0, 1)), None);
+    }
+
+    #[rstest]
+    #[case(0, 0, 'a')]
+    #[case(-1, 0, 'c')]
+    #[case(3, 1, 'd')]
+    #[case(-4, -3, 'f')]
+    fn test_get_wrapping(
+        #[case] x: isize,
+        #[case] y: isize,
+        #[case] expected: char,
+    ) {
+        assert_eq!(
+            *chars("abc\ndef").get_wrapping(x, y),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = chars("...\n...\n...");
+        assert_eq!(
+            grid.neighbours8(p(1, 1)).collect::<Vec<_>>(),
+            vec![
+                p(1, 0),
+                p(2, 0),
+                p(2, 1),
+                p(2, 2),
+                p(1, 2),
+                p(0, 2),
+                p(0, 1),
+                p(0, 0),
+            ]
+        );
+        assert_eq!(
+            grid.neighbours8(p(0, 0)).collect::<Vec<_>>(),
+            vec![p(1, 0), p(1, 1), p(0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours4(p(2, 2)).collect::<Vec<_>>(),
+            vec![p(2, 1), p(1, 2)]
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = chars("abc\ndef");
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![
+                &['a', 'b', 'c'][..],
+                &['d', 'e', 'f'][..]
+            ]
+        );
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+        assert!(grid.row(2).is_none());
+        assert!(grid.column(3).is_none());
+    }
+
+    #[test]
+    fn test_positions() {
+        let grid = chars("ab\ncd");
+        assert_eq!(
+            grid.iter().collect::<Vec<_>>(),
+            vec![
+                (p(0, 0), &'a'),
+                (p(1, 0), &'b'),
+                (p(0, 1), &'c'),
+                (p(1, 1), &'d'),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transform() {
+        let grid = chars("abc\ndef");
+        assert_eq!(
+            grid.transpose().to_string(),
+            "ad\nbe\ncf"
+        );
+        assert_eq!(
+            grid.rotate_clockwise().to_string(),
+            "da\neb\nfc"
+        );
+        assert_eq!(
+            grid.rotate_counterclockwise().to_string(),
+            "cf\nbe\nad"
+        );
+        assert_eq!(
+            grid.rotate_clockwise()
+                .rotate_counterclockwise(),
+            grid
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let grid = Grid::from_vec(
+            2,
+            vec![true, false, false, true],
+        );
+        assert_eq!(
+            grid.map(|&roll| if roll { '@' } else { '.' })
+                .to_string(),
+            "@.\n.@"
+        );
+    }
+}
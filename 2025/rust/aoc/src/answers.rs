@@ -0,0 +1,326 @@
+use std::path::{Path, PathBuf};
+
+use miette::Diagnostic;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{
+    runner::workspace_dir,
+    store::{self, StoreError},
+};
+
+/// How the puzzle server judged a submitted
+/// answer.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// wrong, without a hint which way
+    Incorrect,
+    /// the answer wasn't judged, we have to wait
+    RateLimited,
+    /// the part was already solved or isn't
+    /// unlocked yet
+    WrongLevel,
+}
+
+impl Verdict {
+    /// Whether the answer was checked and turned
+    /// down.
+    pub fn is_rejection(self) -> bool {
+        matches!(
+            self,
+            Verdict::TooHigh
+                | Verdict::TooLow
+                | Verdict::Incorrect
+        )
+    }
+}
+
+/// A single submission for a puzzle part.
+#[derive(
+    Clone, Debug, PartialEq, Eq, Serialize, Deserialize,
+)]
+pub struct Attempt {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// seconds since the unix epoch
+    pub submitted_at: u64,
+}
+
+impl Attempt {
+    pub fn new(
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+        verdict: Verdict,
+    ) -> Self {
+        Attempt {
+            year,
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+            submitted_at: store::now(),
+        }
+    }
+
+    fn is_for(&self, year: u16, day: u8, part: u8) -> bool {
+        self.year == year
+            && self.day == day
+            && self.part == part
+    }
+}
+
+/// Why an answer shouldn't be sent to the
+/// server.
+#[derive(Error, Diagnostic, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    #[error("{answer} was already accepted")]
+    #[diagnostic(code(aoc::submit::already_accepted))]
+    AlreadyAccepted { answer: String },
+    #[error(
+        "this part was already solved with {accepted}, but the solution now gives {answer}"
+    )]
+    #[diagnostic(
+        code(aoc::submit::drifted),
+        help("the solution changed since it was accepted")
+    )]
+    Drifted { answer: String, accepted: String },
+    #[error("{answer} was already rejected as {verdict:?}")]
+    #[diagnostic(code(aoc::submit::rejected))]
+    Rejected { answer: String, verdict: Verdict },
+    #[error(
+        "{answer} is at least {bound}, which was too high"
+    )]
+    #[diagnostic(code(aoc::submit::too_high))]
+    TooHigh { answer: String, bound: String },
+    #[error(
+        "{answer} is at most {bound}, which was too low"
+    )]
+    #[diagnostic(code(aoc::submit::too_low))]
+    TooLow { answer: String, bound: String },
+}
+
+/// Every answer ever submitted, stored as
+/// `answers.toml` at the workspace root.
+#[derive(
+    Clone,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct Answers {
+    #[serde(default, rename = "attempt")]
+    pub attempts: Vec<Attempt>,
+}
+
+impl Answers {
+    /// The default location of the answers file.
+    pub fn default_path() -> PathBuf {
+        workspace_dir().join("answers.toml")
+    }
+
+    /// Reads the answers file, a missing file
+    /// counts as no attempts yet.
+    pub fn load(path: &Path) -> Result<Self, StoreError> {
+        store::load(path)
+    }
+
+    pub fn save(
+        &self,
+        path: &Path,
+    ) -> Result<(), StoreError> {
+        store::save(path, self)
+    }
+
+    pub fn record(&mut self, attempt: Attempt) {
+        self.attempts.push(attempt);
+    }
+
+    /// All attempts for one puzzle part, oldest
+    /// first.
+    pub fn attempts_for(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+    ) -> impl Iterator<Item = &Attempt> {
+        self.attempts.iter().filter(move |attempt| {
+            attempt.is_for(year, day, part)
+        })
+    }
+
+    /// The answer the server accepted for a part.
+    pub fn accepted(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+    ) -> Option<&str> {
+        self.attempts_for(year, day, part)
+            .find(|attempt| {
+                attempt.verdict == Verdict::Correct
+            })
+            .map(|attempt| attempt.answer.as_str())
+    }
+
+    /// Checks `answer` against earlier attempts,
+    /// numeric answers are also compared to the
+    /// too high and too low hints.
+    pub fn check(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<(), Refusal> {
+        if let Some(accepted) =
+            self.accepted(year, day, part)
+        {
+            return Err(if accepted == answer {
+                Refusal::AlreadyAccepted {
+                    answer: answer.to_string(),
+                }
+            } else {
+                Refusal::Drifted {
+                    answer: answer.to_string(),
+                    accepted: accepted.to_string(),
+                }
+            });
+        }
+        let value = answer.parse::<i128>().ok();
+        for attempt in self.attempts_for(year, day, part) {
+            if !attempt.verdict.is_rejection() {
+                continue;
+            }
+            if attempt.answer == answer {
+                return Err(Refusal::Rejected {
+                    answer: answer.to_string(),
+                    verdict: attempt.verdict,
+                });
+            }
+            let (Some(value), Ok(bound)) =
+                (value, attempt.answer.parse::<i128>())
+            else {
+                continue;
+            };
+            match attempt.verdict {
+                Verdict::TooHigh if value >= bound => {
+                    return Err(Refusal::TooHigh {
+                        answer: answer.to_string(),
+                        bound: attempt.answer.clone(),
+                    });
+                }
+                Verdict::TooLow if value <= bound => {
+                    return Err(Refusal::TooLow {
+                        answer: answer.to_string(),
+                        bound: attempt.answer.clone(),
+                    });
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::fs;
+
+    fn history() -> Answers {
+        let mut answers = Answers::default();
+        for (part, answer, verdict) in [
+            (1, "21", Verdict::Correct),
+            (2, "50", Verdict::TooHigh),
+            (2, "10", Verdict::TooLow),
+            (2, "abc", Verdict::Incorrect),
+            (2, "30", Verdict::RateLimited),
+        ] {
+            answers.record(Attempt::new(
+                2025, 7, part, answer, verdict,
+            ));
+        }
+        answers
+    }
+
+    #[rstest]
+    #[case(1, "21", Err(Refusal::AlreadyAccepted { answer: "21".into() }))]
+    #[case(1, "22", Err(Refusal::Drifted { answer: "22".into(), accepted: "21".into() }))]
+    #[case(2, "50", Err(Refusal::Rejected { answer: "50".into(), verdict: Verdict::TooHigh }))]
+    #[case(2, "abc", Err(Refusal::Rejected { answer: "abc".into(), verdict: Verdict::Incorrect }))]
+    #[case(2, "51", Err(Refusal::TooHigh { answer: "51".into(), bound: "50".into() }))]
+    #[case(2, "9", Err(Refusal::TooLow { answer: "9".into(), bound: "10".into() }))]
+    #[case(2, "30", Ok(()))]
+    #[case(2, "49", Ok(()))]
+    #[case(2, "xyz", Ok(()))]
+    fn test_check(
+        #[case] part: u8,
+        #[case] answer: &str,
+        #[case] expected: Result<(), Refusal>,
+    ) {
+        assert_eq!(
+            expected,
+            history().check(2025, 7, part, answer)
+        );
+    }
+
+    #[test]
+    fn test_check_other_puzzle() {
+        assert_eq!(
+            Ok(()),
+            history().check(2025, 8, 2, "50")
+        );
+        assert_eq!(
+            Ok(()),
+            history().check(2024, 7, 1, "22")
+        );
+    }
+
+    #[test]
+    fn test_round_trip() -> miette::Result<()> {
+        let path = std::env::temp_dir().join(format!(
+            "aoc-answers-{}.toml",
+            std::process::id()
+        ));
+        assert_eq!(
+            Answers::default(),
+            Answers::load(&path)?
+        );
+
+        let answers = history();
+        answers.save(&path)?;
+        let contents = fs::read_to_string(&path).unwrap();
+        let loaded = Answers::load(&path)?;
+        fs::remove_file(&path).unwrap();
+
+        assert!(contents.contains("[[attempt]]"));
+        assert!(
+            contents.contains("verdict = \"too-high\"")
+        );
+        assert_eq!(answers, loaded);
+        assert_eq!(Some("21"), loaded.accepted(2025, 7, 1));
+        assert_eq!(None, loaded.accepted(2025, 7, 2));
+        Ok(())
+    }
+}
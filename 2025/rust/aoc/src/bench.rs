@@ -0,0 +1,471 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    runner::workspace_dir,
+    store::{self, StoreError},
+};
+
+/// One row of a divan table, times are in
+/// nanoseconds.
+#[derive(
+    Clone, Debug, PartialEq, Serialize, Deserialize,
+)]
+pub struct Measurement {
+    /// the bench target, e.g. `day_05_bench`
+    pub bench: String,
+    /// the benchmark within the target, nested
+    /// groups are joined with `/`, e.g. `part1`
+    pub name: String,
+    pub fastest_ns: f64,
+    pub slowest_ns: f64,
+    pub median_ns: f64,
+    pub mean_ns: f64,
+    pub samples: u64,
+    pub iters: u64,
+}
+
+/// Reads the measurements out of divan's output,
+/// other lines (cargo's, test harnesses') are
+/// skipped.
+///
+/// ```text
+/// day_05_bench  fastest   │ slowest   │ median    │ mean      │ samples │ iters
+/// ├─ part1      117.2 µs  │ 383.5 µs  │ 163.9 µs  │ 169.3 µs  │ 100     │ 100
+/// ╰─ part2      10.39 µs  │ 25.89 µs  │ 10.89 µs  │ 11.13 µs  │ 100     │ 100
+/// ```
+pub fn parse_divan(output: &str) -> Vec<Measurement> {
+    let mut measurements = Vec::new();
+    let mut bench = None;
+    let mut groups: Vec<String> = Vec::new();
+    for line in output.lines() {
+        if line.contains("fastest")
+            && line.contains("median")
+        {
+            bench = line
+                .split_whitespace()
+                .next()
+                .map(str::to_string);
+            groups.clear();
+            continue;
+        }
+        let Some(bench) = &bench else {
+            continue;
+        };
+        let row = line
+            .trim_start_matches(['│', '├', '╰', '─', ' ']);
+        let indent =
+            line.chars().count() - row.chars().count();
+        if indent == 0 || row.is_empty() {
+            continue;
+        }
+        // every level of nesting is three
+        // characters wide, e.g. `│  ├─ `
+        groups.truncate((indent / 3).saturating_sub(1));
+        let mut columns = row.split('│').map(str::trim);
+        let first = columns.next().unwrap_or_default();
+        let (name, fastest) = first
+            .split_once(char::is_whitespace)
+            .map_or((first, ""), |(name, fastest)| {
+                (name, fastest.trim())
+            });
+        let columns = columns.collect::<Vec<_>>();
+        let (
+            Some(fastest),
+            [slowest, median, mean, samples, iters],
+        ) = (parse_time(fastest), columns.as_slice())
+        else {
+            // a group, its benchmarks follow on the
+            // next lines
+            groups.push(name.to_string());
+            continue;
+        };
+        let (
+            Some(slowest_ns),
+            Some(median_ns),
+            Some(mean_ns),
+            Ok(samples),
+            Ok(iters),
+        ) = (
+            parse_time(slowest),
+            parse_time(median),
+            parse_time(mean),
+            samples.parse(),
+            iters.parse(),
+        )
+        else {
+            continue;
+        };
+        measurements.push(Measurement {
+            bench: bench.clone(),
+            name: groups
+                .iter()
+                .map(String::as_str)
+                .chain([name])
+                .collect::<Vec<_>>()
+                .join("/"),
+            fastest_ns: fastest,
+            slowest_ns,
+            median_ns,
+            mean_ns,
+            samples,
+            iters,
+        });
+    }
+    measurements
+}
+
+/// Parses times like `117.2 µs` into
+/// nanoseconds.
+fn parse_time(time: &str) -> Option<f64> {
+    let (value, unit) = time.split_once(' ')?;
+    let scale = match unit.trim() {
+        "ps" => 1e-3,
+        "ns" => 1.0,
+        "µs" | "us" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+    Some(value.parse::<f64>().ok()? * scale)
+}
+
+/// Formats nanoseconds the way divan does.
+pub fn format_time(nanoseconds: f64) -> String {
+    let (value, unit) = match nanoseconds {
+        ns if ns >= 1e9 => (ns / 1e9, "s"),
+        ns if ns >= 1e6 => (ns / 1e6, "ms"),
+        ns if ns >= 1e3 => (ns / 1e3, "µs"),
+        ns => (ns, "ns"),
+    };
+    // four significant digits, e.g. `163.9 µs`
+    let precision = match value {
+        value if value >= 100.0 => 1,
+        value if value >= 10.0 => 2,
+        _ => 3,
+    };
+    let value = format!("{value:.precision$}");
+    let value =
+        value.trim_end_matches('0').trim_end_matches('.');
+    format!("{value} {unit}")
+}
+
+/// A measurement taken at a point in the
+/// repository's history.
+#[derive(
+    Clone, Debug, PartialEq, Serialize, Deserialize,
+)]
+pub struct Record {
+    pub commit: String,
+    /// seconds since the unix epoch
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub measurement: Measurement,
+}
+
+/// How a benchmark's median moved since its
+/// previous record.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison<'a> {
+    pub previous: &'a Record,
+    pub current: &'a Record,
+}
+
+impl Comparison<'_> {
+    /// The relative change of the median, `0.1`
+    /// is 10% slower.
+    pub fn change(&self) -> f64 {
+        self.current.measurement.median_ns
+            / self.previous.measurement.median_ns
+            - 1.0
+    }
+}
+
+/// Every recorded benchmark run, stored as
+/// `benches.toml` at the workspace root.
+#[derive(
+    Clone, Debug, Default, PartialEq, Serialize, Deserialize,
+)]
+pub struct History {
+    #[serde(default, rename = "record")]
+    pub records: Vec<Record>,
+}
+
+impl History {
+    /// The default location of the history file.
+    pub fn default_path() -> PathBuf {
+        workspace_dir().join("benches.toml")
+    }
+
+    pub fn load(path: &Path) -> Result<Self, StoreError> {
+        store::load(path)
+    }
+
+    pub fn save(
+        &self,
+        path: &Path,
+    ) -> Result<(), StoreError> {
+        store::save(path, self)
+    }
+
+    /// Appends the measurements of one run.
+    pub fn record(
+        &mut self,
+        commit: &str,
+        timestamp: u64,
+        measurements: impl IntoIterator<Item = Measurement>,
+    ) {
+        self.records.extend(measurements.into_iter().map(
+            |measurement| Record {
+                commit: commit.to_string(),
+                timestamp,
+                measurement,
+            },
+        ));
+    }
+
+    /// Pairs the latest record of every benchmark
+    /// with the one before it, in the order the
+    /// benchmarks were first recorded.
+    pub fn compare(&self) -> Vec<Comparison<'_>> {
+        let mut order = Vec::new();
+        let mut runs: HashMap<(&str, &str), Vec<&Record>> =
+            HashMap::new();
+        for record in &self.records {
+            let key = (
+                record.measurement.bench.as_str(),
+                record.measurement.name.as_str(),
+            );
+            runs.entry(key)
+                .or_insert_with(|| {
+                    order.push(key);
+                    Vec::new()
+                })
+                .push(record);
+        }
+        order
+            .into_iter()
+            .filter_map(|key| match runs[&key].as_slice() {
+                [.., previous, current] => {
+                    Some(Comparison { previous, current })
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// The commit the workspace is at, marked
+/// `-dirty` when there are uncommitted changes.
+pub fn current_commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .current_dir(workspace_dir())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| {
+                String::from_utf8_lossy(&output.stdout)
+                    .trim()
+                    .to_string()
+            })
+    };
+    let Some(commit) =
+        git(&["rev-parse", "--short", "HEAD"])
+    else {
+        return "unknown".to_string();
+    };
+    match git(&["status", "--porcelain"]) {
+        Some(status) if !status.is_empty() => {
+            format!("{commit}-dirty")
+        }
+        _ => commit,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    const OUTPUT: &str = "   Compiling day-05 v0.1.0
+    Finished `bench` profile [optimized] target(s) in 1.23s
+     Running benches/benchmarks.rs (target/release/deps/day_05_bench-0f1e)
+Timer precision: 20 ns
+day_05_bench  fastest       │ slowest       │ median        │ mean          │ samples │ iters
+├─ part1      117.2 µs      │ 383.5 µs      │ 163.9 µs      │ 169.3 µs      │ 100     │ 100
+╰─ part2      10.39 µs      │ 25.89 µs      │ 10.89 µs      │ 11.13 µs      │ 100     │ 100
+
+day_08_bench  fastest       │ slowest       │ median        │ mean          │ samples │ iters
+├─ part1                    │               │               │               │         │
+│  ├─ parse   31.2 µs       │ 40 µs         │ 32 µs         │ 33 µs         │ 100     │ 100
+│  ╰─ solve   268.8 ms      │ 300.3 ms      │ 273.4 ms      │ 275 ms        │ 100     │ 100
+╰─ part2      1.5 s         │ 1.7 s         │ 1.6 s         │ 1.6 s         │ 10      │ 10
+";
+
+    #[test]
+    fn test_parse_divan() {
+        let measurements = parse_divan(OUTPUT);
+        let names = measurements
+            .iter()
+            .map(|measurement| {
+                format!(
+                    "{} {}",
+                    measurement.bench, measurement.name
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "day_05_bench part1",
+                "day_05_bench part2",
+                "day_08_bench part1/parse",
+                "day_08_bench part1/solve",
+                "day_08_bench part2",
+            ]
+        );
+        assert_eq!(
+            measurements[0],
+            Measurement {
+                bench: "day_05_bench".to_string(),
+                name: "part1".to_string(),
+                fastest_ns: 117_200.0,
+                slowest_ns: 383_500.0,
+                median_ns: 163_900.0,
+                mean_ns: 169_300.0,
+                samples: 100,
+                iters: 100,
+            }
+        );
+        assert_eq!(
+            measurements[3].median_ns,
+            273_400_000.0
+        );
+        assert_eq!(measurements[4].samples, 10);
+    }
+
+    #[test]
+    fn test_parse_divan_shallow_indent() {
+        // rows indented by less than a level don't
+        // belong to a group
+        let output = "\
+day_05_bench  fastest   │ slowest   │ median    │ mean      │ samples │ iters
+├─ group                │           │           │           │         │
+ part1        1 µs      │ 2 µs      │ 1 µs      │ 1 µs      │ 100     │ 100
+╰ part2       1 µs      │ 2 µs      │ 1 µs      │ 1 µs      │ 100     │ 100
+";
+        let names = parse_divan(output)
+            .into_iter()
+            .map(|measurement| measurement.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["part1", "part2"]);
+    }
+
+    #[rstest]
+    #[case("20 ns", Some(20.0))]
+    #[case("1.5 µs", Some(1_500.0))]
+    #[case("2 ms", Some(2_000_000.0))]
+    #[case("1 s", Some(1_000_000_000.0))]
+    #[case("500 ps", Some(0.5))]
+    #[case("1 min", None)]
+    #[case("", None)]
+    fn test_parse_time(
+        #[case] time: &str,
+        #[case] expected: Option<f64>,
+    ) {
+        assert_eq!(parse_time(time), expected);
+    }
+
+    #[rstest]
+    #[case(20.0, "20 ns")]
+    #[case(163_900.0, "163.9 µs")]
+    #[case(10_390.0, "10.39 µs")]
+    #[case(273_400_000.0, "273.4 ms")]
+    #[case(2e9, "2 s")]
+    fn test_format_time(
+        #[case] nanoseconds: f64,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(format_time(nanoseconds), expected);
+    }
+
+    fn measurement(
+        name: &str,
+        median_ns: f64,
+    ) -> Measurement {
+        Measurement {
+            bench: "day_05_bench".to_string(),
+            name: name.to_string(),
+            fastest_ns: median_ns,
+            slowest_ns: median_ns,
+            median_ns,
+            mean_ns: median_ns,
+            samples: 100,
+            iters: 100,
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let mut history = History::default();
+        history.record(
+            "aaaaaaa",
+            1,
+            [
+                measurement("part1", 100.0),
+                measurement("part2", 100.0),
+            ],
+        );
+        history.record(
+            "bbbbbbb",
+            2,
+            [measurement("part1", 150.0)],
+        );
+        history.record(
+            "ccccccc",
+            3,
+            [measurement("part1", 120.0)],
+        );
+
+        let comparisons = history.compare();
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(
+            comparisons[0].previous.commit,
+            "bbbbbbb"
+        );
+        assert_eq!(
+            comparisons[0].current.commit,
+            "ccccccc"
+        );
+        assert!(
+            (comparisons[0].change() + 0.2).abs() < 1e-9
+        );
+    }
+
+    #[test]
+    fn test_round_trip() -> miette::Result<()> {
+        let path = std::env::temp_dir().join(format!(
+            "aoc-benches-{}.toml",
+            std::process::id()
+        ));
+        let mut history = History::default();
+        history.record("aaaaaaa", 1, parse_divan(OUTPUT));
+        history.save(&path)?;
+        let contents =
+            std::fs::read_to_string(&path).unwrap();
+        let loaded = History::load(&path)?;
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(contents.contains("[[record]]"));
+        assert!(contents.contains("median_ns = 163900.0"));
+        assert_eq!(history, loaded);
+        Ok(())
+    }
+}
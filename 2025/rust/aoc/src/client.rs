@@ -0,0 +1,382 @@
+use std::time::Duration;
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+use crate::{answers::Verdict, html::text};
+
+pub const SESSION_VAR: &str = "SESSION";
+pub const BASE_URL_VAR: &str = "AOC_BASE_URL";
+pub const DEFAULT_BASE_URL: &str =
+    "https://adventofcode.com";
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum ClientError {
+    #[error("request to the puzzle server failed")]
+    Http(#[from] reqwest::Error),
+    #[error("couldn't make sense of the server's reply")]
+    #[diagnostic(
+        code(aoc::submit::unrecognised),
+        help("the reply was:\n{message}")
+    )]
+    Unrecognised { message: String },
+}
+
+/// What the server said about a submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reply {
+    pub verdict: Verdict,
+    /// how long to wait before submitting again,
+    /// only known when rate limited
+    pub wait: Option<Duration>,
+    /// the text of the reply, without markup
+    pub message: String,
+}
+
+/// Talks to the puzzle server on behalf of a
+/// logged in user.
+#[derive(Debug)]
+pub struct Client {
+    base_url: String,
+    session: String,
+    http: reqwest::blocking::Client,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Client {
+            base_url: base_url
+                .trim_end_matches('/')
+                .to_string(),
+            session: session.to_string(),
+            http: reqwest::blocking::Client::new(),
+        }
+    }
+
+    /// The puzzle page of a day, including part 2
+    /// once part 1 is solved.
+    pub fn page(
+        &self,
+        year: u16,
+        day: u8,
+    ) -> Result<String, ClientError> {
+        self.get(&format!("{year}/day/{day}"))
+    }
+
+    /// The puzzle input of a day, which differs
+    /// per account.
+    pub fn input(
+        &self,
+        year: u16,
+        day: u8,
+    ) -> Result<String, ClientError> {
+        self.get(&format!("{year}/day/{day}/input"))
+    }
+
+    fn get(
+        &self,
+        path: &str,
+    ) -> Result<String, ClientError> {
+        Ok(self
+            .http
+            .get(format!("{}/{path}", self.base_url))
+            .header(
+                reqwest::header::COOKIE,
+                format!("session={}", self.session),
+            )
+            .send()?
+            .error_for_status()?
+            .text()?)
+    }
+
+    /// Posts `answer` for a puzzle part.
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<Reply, ClientError> {
+        let level = part.to_string();
+        let html = self
+            .http
+            .post(format!(
+                "{}/{year}/day/{day}/answer",
+                self.base_url
+            ))
+            .header(
+                reqwest::header::COOKIE,
+                format!("session={}", self.session),
+            )
+            .form(&[
+                ("level", level.as_str()),
+                ("answer", answer),
+            ])
+            .send()?
+            .error_for_status()?
+            .text()?;
+        parse_reply(&html)
+    }
+}
+
+/// Reads the verdict out of the answer page.
+pub fn parse_reply(
+    html: &str,
+) -> Result<Reply, ClientError> {
+    let message = article_text(html);
+    let verdict = if message
+        .contains("That's the right answer")
+    {
+        Verdict::Correct
+    } else if message
+        .contains("That's not the right answer")
+    {
+        if message.contains("too high") {
+            Verdict::TooHigh
+        } else if message.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Incorrect
+        }
+    } else if message
+        .contains("You gave an answer too recently")
+    {
+        Verdict::RateLimited
+    } else if message.contains("solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        return Err(ClientError::Unrecognised { message });
+    };
+    let wait = (verdict == Verdict::RateLimited)
+        .then(|| parse_wait(&message))
+        .flatten();
+    Ok(Reply {
+        verdict,
+        wait,
+        message,
+    })
+}
+
+/// The text of the page's `<article>`, or of the
+/// whole page if there is none.
+fn article_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(html, |(article, _)| article);
+    text(article)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parses `You have 1m 5s left to wait`.
+fn parse_wait(message: &str) -> Option<Duration> {
+    let (_, rest) = message.split_once("You have ")?;
+    let (amount, _) = rest.split_once(" left to wait")?;
+    amount.split_whitespace().try_fold(
+        Duration::ZERO,
+        |total, token| {
+            let (value, seconds_per_unit) =
+                [("h", 60 * 60), ("m", 60), ("s", 1)]
+                    .into_iter()
+                    .find_map(|(unit, seconds)| {
+                        Some((
+                            token.strip_suffix(unit)?,
+                            seconds,
+                        ))
+                    })?;
+            let seconds = value
+                .parse::<u64>()
+                .ok()?
+                .checked_mul(seconds_per_unit)?;
+            total.checked_add(Duration::from_secs(seconds))
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    const CORRECT: &str = "<main><article><p>That's the right answer!  You are <em>one gold star</em> closer to decorating the North Pole. <a href=\"/2025/day/7#part2\">[Continue to Part Two]</a></p></article></main>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again. <a href=\"/2025/day/7\">[Return to Day 7]</a></p></article>";
+    const TOO_LOW: &str = "<article><p>That's not the right answer; your answer is too low.  Please wait one minute before trying again.</p></article>";
+    const INCORRECT: &str = "<article><p>That's not the right answer.  If you're stuck, make sure you're using the full input data.</p></article>";
+    const RATE_LIMITED: &str = "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. <a href=\"/2025/day/7\">[Return to Day 7]</a></p></article>";
+    const WRONG_LEVEL: &str = "<article><p>You don't seem to be solving the right level.  Did you already complete it? <a href=\"/2025/day/7\">[Return to Day 7]</a></p></article>";
+
+    #[rstest]
+    #[case(CORRECT, Verdict::Correct, None)]
+    #[case(TOO_HIGH, Verdict::TooHigh, None)]
+    #[case(TOO_LOW, Verdict::TooLow, None)]
+    #[case(INCORRECT, Verdict::Incorrect, None)]
+    #[case(RATE_LIMITED, Verdict::RateLimited, Some(65))]
+    #[case(WRONG_LEVEL, Verdict::WrongLevel, None)]
+    fn test_parse_reply(
+        #[case] html: &str,
+        #[case] verdict: Verdict,
+        #[case] wait: Option<u64>,
+    ) -> miette::Result<()> {
+        let reply = parse_reply(html)?;
+        assert_eq!(verdict, reply.verdict);
+        assert_eq!(
+            wait.map(Duration::from_secs),
+            reply.wait
+        );
+        assert!(!reply.message.contains('<'));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("You have 35s left to wait.", Some(35))]
+    #[case("You have 2m left to wait.", Some(120))]
+    #[case("You have 1h 2m 3s left to wait.", Some(3723))]
+    #[case("You have soon left to wait.", None)]
+    #[case("You have 5é left to wait.", None)]
+    #[case("You have é left to wait.", None)]
+    #[case("You have h left to wait.", None)]
+    #[case(
+        "You have 18446744073709551615h left to wait.",
+        None
+    )]
+    #[case("Please wait.", None)]
+    fn test_parse_wait(
+        #[case] message: &str,
+        #[case] expected: Option<u64>,
+    ) {
+        assert_eq!(
+            expected.map(Duration::from_secs),
+            parse_wait(message)
+        );
+    }
+
+    #[test]
+    fn test_parse_reply_unrecognised() {
+        assert!(matches!(
+            parse_reply(
+                "<html>Puzzle inputs differ by user.</html>"
+            ),
+            Err(ClientError::Unrecognised { .. })
+        ));
+    }
+
+    /// Serves a single request with `status` and
+    /// `body`, handing back the raw request.
+    fn serve(
+        status: &str,
+        body: &'static str,
+    ) -> (String, JoinHandle<String>) {
+        let listener =
+            TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!(
+            "http://{}",
+            listener.local_addr().unwrap()
+        );
+        let status = status.to_string();
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some((name, value)) =
+                    line.split_once(':')
+                    && name.eq_ignore_ascii_case(
+                        "content-length",
+                    )
+                {
+                    length = value.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut form = vec![0; length];
+            reader.read_exact(&mut form).unwrap();
+            request.push_str(
+                &String::from_utf8(form).unwrap(),
+            );
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {status}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            request
+        });
+        (base_url, handle)
+    }
+
+    #[test]
+    fn test_submit() -> miette::Result<()> {
+        let (base_url, server) = serve("200 OK", TOO_LOW);
+        let reply =
+            Client::new(&format!("{base_url}/"), "cookie")
+                .submit(2025, 7, 2, "1234")?;
+        let request = server.join().unwrap();
+
+        assert_eq!(Verdict::TooLow, reply.verdict);
+        assert!(
+            request.starts_with("POST /2025/day/7/answer ")
+        );
+        assert!(
+            request.contains("cookie: session=cookie\r\n")
+        );
+        assert!(request.ends_with("level=2&answer=1234"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_page() -> miette::Result<()> {
+        let (base_url, server) = serve("200 OK", CORRECT);
+        let page = Client::new(&base_url, "cookie")
+            .page(2025, 7)?;
+        let request = server.join().unwrap();
+
+        assert_eq!(CORRECT, page);
+        assert!(request.starts_with("GET /2025/day/7 "));
+        assert!(
+            request.contains("cookie: session=cookie\r\n")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_input() -> miette::Result<()> {
+        let (base_url, server) = serve("200 OK", "1,2\n");
+        let input = Client::new(&base_url, "cookie")
+            .input(2025, 9)?;
+        let request = server.join().unwrap();
+
+        assert_eq!("1,2\n", input);
+        assert!(
+            request.starts_with("GET /2025/day/9/input ")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_submit_error_status() {
+        let (base_url, server) =
+            serve("500 Internal Server Error", "oops");
+        let result = Client::new(&base_url, "cookie")
+            .submit(2025, 7, 1, "1");
+        server.join().unwrap();
+
+        assert!(matches!(
+            result,
+            Err(ClientError::Http(_))
+        ));
+    }
+}
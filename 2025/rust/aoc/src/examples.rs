@@ -0,0 +1,191 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::html::{sections, text};
+
+/// An example pulled out of a puzzle page.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Extracted {
+    pub part: u8,
+    pub input: String,
+    /// the last emphasised answer of the part's
+    /// description
+    pub expected: Option<String>,
+}
+
+/// Finds the example of every part on a puzzle
+/// page.
+///
+/// Each `<article class="day-desc">` describes a
+/// part. Its first `<pre><code>` block is taken
+/// as the example, part 2 reuses the previous
+/// example when it doesn't show a new one. The
+/// expected answer is the last `<code><em>` of
+/// the article.
+pub fn extract(html: &str) -> Vec<Extracted> {
+    let mut examples = Vec::new();
+    let mut previous: Option<String> = None;
+    for (part, article) in (1..).zip(sections(
+        html,
+        "<article class=\"day-desc\">",
+        "</article>",
+    )) {
+        let input = sections(
+            article,
+            "<pre><code>",
+            "</code></pre>",
+        )
+        .next()
+        .map(text)
+        .or_else(|| previous.clone());
+        let Some(input) = input else {
+            continue;
+        };
+        let expected =
+            sections(article, "<code><em>", "</em></code>")
+                .last()
+                .map(text);
+        previous = Some(input.clone());
+        examples.push(Extracted {
+            part,
+            input,
+            expected,
+        });
+    }
+    examples
+}
+
+/// Writes the examples as `partN-1.txt` and
+/// `partN-1.expected` into `dir`, returning the
+/// paths written. Further examples can be added
+/// by hand as `partN-2.txt` and so on.
+pub fn write(
+    dir: &Path,
+    examples: &[Extracted],
+) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+    let mut written = Vec::new();
+    for example in examples {
+        let path =
+            dir.join(format!("part{}-1.txt", example.part));
+        fs::write(&path, &example.input)?;
+        written.push(path.clone());
+        if let Some(expected) = &example.expected {
+            let path = path.with_extension("expected");
+            fs::write(&path, format!("{expected}\n"))?;
+            written.push(path);
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 7: Laboratories ---</h2>
+<p>For example:</p>
+<pre><code>.S.
+.^.
+&lt;&amp;&gt;
+</code></pre>
+<p>Then it looks like this:</p>
+<pre><code>.S.
+|^|
+</code></pre>
+<p>The beam is split <code><em>21</em></code> times.</p>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>In the example above, there are <em>many</em> timelines, <code><em>2</em></code> end up left and <code><em>40</em></code> in total.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_extract() {
+        let input = ".S.\n.^.\n<&>\n".to_string();
+        assert_eq!(
+            extract(PAGE),
+            vec![
+                Extracted {
+                    part: 1,
+                    input: input.clone(),
+                    expected: Some("21".to_string()),
+                },
+                Extracted {
+                    part: 2,
+                    input,
+                    expected: Some("40".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_part1_only() {
+        let page =
+            PAGE.split("<p>Your puzzle").next().unwrap();
+        assert_eq!(extract(page).len(), 1);
+        assert_eq!(
+            extract("<main>Please log in</main>"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_extract_emphasised_code() {
+        let page = r#"<article class="day-desc"><pre><code>1 2
+<em>3</em> 4
+</code></pre><p>Which gives <code><em>7</em></code>.</p></article>"#;
+        assert_eq!(
+            extract(page),
+            vec![Extracted {
+                part: 1,
+                input: "1 2\n3 4\n".to_string(),
+                expected: Some("7".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_write() -> io::Result<()> {
+        let dir = std::env::temp_dir().join(format!(
+            "aoc-extracted-{}",
+            std::process::id()
+        ));
+        let written = write(
+            &dir,
+            &[
+                Extracted {
+                    part: 1,
+                    input: "1\n".to_string(),
+                    expected: Some("2".to_string()),
+                },
+                Extracted {
+                    part: 2,
+                    input: "1\n".to_string(),
+                    expected: None,
+                },
+            ],
+        )?;
+        let expected = fs::read_to_string(
+            dir.join("part1-1.expected"),
+        );
+        fs::remove_dir_all(&dir)?;
+
+        assert_eq!(
+            written,
+            [
+                "part1-1.txt",
+                "part1-1.expected",
+                "part2-1.txt"
+            ]
+            .map(|name| dir.join(name))
+        );
+        assert_eq!(expected?, "2\n");
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+//! Just enough HTML handling for the puzzle
+//! pages, which are simple and well formed.
+
+/// The text between each `start` and the `end`
+/// following it.
+pub fn sections<'a>(
+    html: &'a str,
+    start: &'a str,
+    end: &'a str,
+) -> impl Iterator<Item = &'a str> {
+    html.split(start).skip(1).filter_map(move |rest| {
+        rest.split_once(end).map(|(section, _)| section)
+    })
+}
+
+/// Drops the markup of `html` and decodes the
+/// entities the puzzle pages use.
+pub fn text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for ch in html.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            ch if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+    decode(&text)
+}
+
+/// Decodes the entities the puzzle pages use.
+pub fn decode(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sections() {
+        let html = "<b>1</b> <i>2</i> <b>3</b> <b>4";
+        assert_eq!(
+            sections(html, "<b>", "</b>")
+                .collect::<Vec<_>>(),
+            ["1", "3"]
+        );
+    }
+
+    #[test]
+    fn test_text() {
+        assert_eq!(
+            text("<p>a &lt;<em>b</em>&gt; &amp;lt;</p>"),
+            "a <b> &lt;"
+        );
+    }
+}
@@ -0,0 +1,11 @@
+pub mod answers;
+pub mod bench;
+pub mod client;
+pub mod examples;
+mod html;
+pub mod puzzle;
+pub mod registry;
+pub mod regression;
+pub mod report;
+pub mod runner;
+pub mod store;
@@ -0,0 +1,474 @@
+use std::path::PathBuf;
+
+use aoc::{
+    answers::{Answers, Attempt, Refusal, Verdict},
+    bench::{self, History},
+    client::{self, Client},
+    examples, registry,
+    regression::{self, Status},
+    report::Report,
+    runner,
+};
+use aoc_core::input::Source;
+use clap::{Parser, Subcommand, ValueEnum};
+use miette::{Context, IntoDiagnostic, miette};
+
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Args {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// run a solution against its puzzle input,
+    /// e.g. `aoc run 2025 7 2`
+    Run {
+        #[clap(required_unless_present = "all")]
+        year: Option<u16>,
+        #[clap(required_unless_present = "all")]
+        day: Option<u8>,
+        /// leave out to run both parts
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// run every registered solution
+        #[clap(long, conflicts_with_all = ["year", "day", "part", "input"])]
+        all: bool,
+        /// read the input from this file instead
+        /// of `day-NN/input.txt`, `-` reads
+        /// stdin
+        #[clap(short, long)]
+        input: Option<String>,
+    },
+    /// run a solution and submit its answer,
+    /// e.g. `aoc submit 7 2`
+    Submit {
+        day: u8,
+        #[clap(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        #[clap(long, default_value_t = registry::latest_year())]
+        year: u16,
+        /// read the input from this file instead
+        /// of `day-NN/input.txt`, `-` reads
+        /// stdin
+        #[clap(short, long)]
+        input: Option<String>,
+        /// the session cookie of your account
+        #[clap(long, env = client::SESSION_VAR, hide_env_values = true)]
+        session: String,
+        #[clap(long, env = client::BASE_URL_VAR, default_value = client::DEFAULT_BASE_URL)]
+        base_url: String,
+        /// where attempts are recorded, defaults
+        /// to `answers.toml` in the workspace
+        #[clap(long)]
+        answers: Option<PathBuf>,
+    },
+    /// extract the examples of a puzzle page into
+    /// `day-NN/examples`, e.g. `aoc examples 7`
+    Examples {
+        day: u8,
+        #[clap(long, default_value_t = registry::latest_year())]
+        year: u16,
+        /// read a saved copy of the puzzle page
+        /// instead of fetching it
+        #[clap(long)]
+        page: Option<PathBuf>,
+        /// the session cookie of your account,
+        /// needed to see part 2
+        #[clap(long, env = client::SESSION_VAR, hide_env_values = true)]
+        session: Option<String>,
+        #[clap(long, env = client::BASE_URL_VAR, default_value = client::DEFAULT_BASE_URL)]
+        base_url: String,
+    },
+    /// keep track of benchmark results
+    Bench {
+        #[clap(subcommand)]
+        command: BenchCommand,
+    },
+    /// check that every solution still gives the
+    /// answer accepted for it in `answers.toml`
+    Verify {
+        #[clap(long)]
+        answers: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum BenchCommand {
+    /// parse divan output and add it to the
+    /// history, e.g.
+    /// `cargo bench | aoc bench record`
+    Record {
+        /// read the divan output from this file
+        /// instead of stdin
+        #[clap(short, long)]
+        input: Option<PathBuf>,
+        /// defaults to `benches.toml` in the
+        /// workspace
+        #[clap(long)]
+        history: Option<PathBuf>,
+    },
+    /// flag benchmarks whose median regressed
+    /// since their previous record
+    Compare {
+        /// how much slower, in percent, counts as
+        /// a regression
+        #[clap(long, default_value_t = 10.0)]
+        threshold: f64,
+        #[clap(long)]
+        history: Option<PathBuf>,
+    },
+    /// summarise the latest results of every day,
+    /// run `just bench-all` first to refresh them
+    Report {
+        #[clap(long, value_enum, default_value_t = Format::Markdown)]
+        format: Format,
+        /// how many of the slowest parts to
+        /// highlight
+        #[clap(long, default_value_t = 3)]
+        slowest: usize,
+        /// write the report here instead of
+        /// stdout
+        #[clap(short, long)]
+        output: Option<PathBuf>,
+        #[clap(long)]
+        history: Option<PathBuf>,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Format {
+    Markdown,
+    Json,
+}
+
+#[tracing::instrument]
+fn main() -> miette::Result<()> {
+    tracing_subscriber::fmt::init();
+
+    match Args::parse().command {
+        Command::Run {
+            year,
+            day,
+            part,
+            all,
+            input,
+        } => {
+            let entries = if all {
+                registry::all().collect::<Vec<_>>()
+            } else {
+                let (Some(year), Some(day)) = (year, day)
+                else {
+                    unreachable!(
+                        "clap requires year and day without --all"
+                    );
+                };
+                registry::find(year, day, part).collect()
+            };
+            if entries.is_empty() {
+                return Err(miette!(
+                    "no solution registered for that puzzle"
+                ));
+            }
+            // an explicit input is read once, so `-` can
+            // feed both parts
+            let explicit = input
+                .map(|arg| Source::from_arg(&arg).read())
+                .transpose()?;
+            for entry in entries {
+                let result = match &explicit {
+                    Some(input) => {
+                        runner::run(&entry, input)
+                    }
+                    None => runner::input_source(&entry)
+                        .read()
+                        .map_err(miette::Report::from)
+                        .and_then(|input| {
+                            runner::run(&entry, &input)
+                        }),
+                };
+                match result {
+                    Ok(outcome) => println!(
+                        "{} day {:02} part {}: {} ({:?}, parse {:?}, solve {:?})",
+                        entry.year(),
+                        entry.day(),
+                        entry.part,
+                        outcome.answer,
+                        outcome.total(),
+                        outcome.parse,
+                        outcome.solve
+                    ),
+                    // keep going so a single missing
+                    // input doesn't hide the other days
+                    Err(report) if all => {
+                        eprintln!("{report:?}")
+                    }
+                    Err(report) => return Err(report),
+                }
+            }
+        }
+        Command::Submit {
+            day,
+            part,
+            year,
+            input,
+            session,
+            base_url,
+            answers,
+        } => {
+            let Some(entry) =
+                registry::find(year, day, Some(part))
+                    .next()
+            else {
+                return Err(miette!(
+                    "no solution registered for that puzzle"
+                ));
+            };
+            let input = match input {
+                Some(arg) => Source::from_arg(&arg).read(),
+                None => runner::input_source(&entry).read(),
+            }?;
+            let answer =
+                runner::run(&entry, &input)?.answer;
+
+            let path = answers
+                .unwrap_or_else(Answers::default_path);
+            let mut answers = Answers::load(&path)?;
+            match answers.check(year, day, part, &answer) {
+                Ok(()) => {}
+                Err(Refusal::AlreadyAccepted {
+                    ..
+                }) => {
+                    println!(
+                        "{answer} was already accepted"
+                    );
+                    return Ok(());
+                }
+                Err(refusal) => return Err(refusal.into()),
+            }
+
+            println!(
+                "submitting {answer} for {year} day {day:02} part {part}"
+            );
+            let reply = Client::new(&base_url, &session)
+                .submit(year, day, part, &answer)?;
+            answers.record(Attempt::new(
+                year,
+                day,
+                part,
+                &answer,
+                reply.verdict,
+            ));
+            answers.save(&path)?;
+
+            println!("{}", reply.message);
+            match (reply.verdict, reply.wait) {
+                (Verdict::RateLimited, Some(wait)) => {
+                    println!("try again in {wait:?}")
+                }
+                (verdict, _) => println!("{verdict:?}"),
+            }
+        }
+        Command::Examples {
+            day,
+            year,
+            page,
+            session,
+            base_url,
+        } => {
+            let html = match (page, session) {
+                (Some(page), _) => {
+                    std::fs::read_to_string(&page)
+                        .into_diagnostic()
+                        .wrap_err_with(|| {
+                            format!(
+                                "read {}",
+                                page.display()
+                            )
+                        })?
+                }
+                (None, Some(session)) => {
+                    Client::new(&base_url, &session)
+                        .page(year, day)?
+                }
+                (None, None) => {
+                    return Err(miette!(
+                        help = "set SESSION or pass a saved page with --page",
+                        "no session to fetch the puzzle page with"
+                    ));
+                }
+            };
+            let extracted = examples::extract(&html);
+            if extracted.is_empty() {
+                return Err(miette!(
+                    "no examples found on the puzzle page"
+                ));
+            }
+            let written = examples::write(
+                &runner::day_dir(day).join("examples"),
+                &extracted,
+            )
+            .into_diagnostic()?;
+            for path in written {
+                println!("wrote {}", path.display());
+            }
+            for example in
+                extracted.iter().filter(|example| {
+                    example.expected.is_none()
+                })
+            {
+                eprintln!(
+                    "no expected answer found for part {}",
+                    example.part
+                );
+            }
+        }
+        Command::Bench {
+            command: BenchCommand::Record { input, history },
+        } => {
+            let output =
+                match input {
+                    Some(path) => {
+                        std::fs::read_to_string(&path)
+                    }
+                    None => std::io::read_to_string(
+                        std::io::stdin(),
+                    ),
+                }
+                .into_diagnostic()
+                .wrap_err("read the divan output")?;
+            let measurements = bench::parse_divan(&output);
+            if measurements.is_empty() {
+                return Err(miette!(
+                    "no divan results found in the output"
+                ));
+            }
+            let path = history
+                .unwrap_or_else(History::default_path);
+            let mut history = History::load(&path)?;
+            let commit = bench::current_commit();
+            println!(
+                "recording {} results for {commit}",
+                measurements.len()
+            );
+            history.record(
+                &commit,
+                aoc::store::now(),
+                measurements,
+            );
+            history.save(&path)?;
+        }
+        Command::Bench {
+            command:
+                BenchCommand::Compare { threshold, history },
+        } => {
+            let history = History::load(
+                &history
+                    .unwrap_or_else(History::default_path),
+            )?;
+            let mut regressed = 0;
+            for comparison in history.compare() {
+                let change = comparison.change() * 100.0;
+                let flag = if change > threshold {
+                    regressed += 1;
+                    " REGRESSED"
+                } else {
+                    ""
+                };
+                let current =
+                    &comparison.current.measurement;
+                println!(
+                    "{} {}: {} ({}) -> {} ({}), {change:+.1}%{flag}",
+                    current.bench,
+                    current.name,
+                    bench::format_time(
+                        comparison
+                            .previous
+                            .measurement
+                            .median_ns
+                    ),
+                    comparison.previous.commit,
+                    bench::format_time(current.median_ns),
+                    comparison.current.commit,
+                );
+            }
+            if regressed > 0 {
+                return Err(miette!(
+                    "{regressed} benchmarks regressed by more than {threshold}%"
+                ));
+            }
+        }
+        Command::Bench {
+            command:
+                BenchCommand::Report {
+                    format,
+                    slowest,
+                    output,
+                    history,
+                },
+        } => {
+            let history = History::load(
+                &history
+                    .unwrap_or_else(History::default_path),
+            )?;
+            let report = Report::new(&history, slowest);
+            let report = match format {
+                Format::Markdown => report.to_markdown(),
+                Format::Json => report.to_json(),
+            };
+            match output {
+                Some(path) => std::fs::write(&path, report)
+                    .into_diagnostic()
+                    .wrap_err_with(|| {
+                        format!("write {}", path.display())
+                    })?,
+                None => print!("{report}"),
+            }
+        }
+        Command::Verify { answers } => {
+            let answers = Answers::load(
+                &answers
+                    .unwrap_or_else(Answers::default_path),
+            )?;
+            let mut drifted = 0;
+            for entry in registry::all() {
+                let status = regression::verify(
+                    &answers,
+                    &entry,
+                    &runner::input_source(&entry),
+                )?;
+                let status = match status {
+                    Status::Verified => "ok".to_string(),
+                    Status::Unpinned => {
+                        "no accepted answer".to_string()
+                    }
+                    Status::MissingInput => {
+                        "skipped, no input".to_string()
+                    }
+                    Status::Drifted {
+                        expected,
+                        actual,
+                    } => {
+                        drifted += 1;
+                        format!(
+                            "DRIFTED, expected {expected}, got {actual}"
+                        )
+                    }
+                };
+                println!(
+                    "{} day {:02} part {}: {status}",
+                    entry.year(),
+                    entry.day(),
+                    entry.part
+                );
+            }
+            if drifted > 0 {
+                return Err(miette!(
+                    "{drifted} drifted from their accepted answer"
+                ));
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,325 @@
+//! Turning a puzzle page into the Markdown of
+//! `PUZZLE.md`.
+
+use crate::html::{decode, sections};
+
+enum Token<'a> {
+    Open { name: &'a str, attributes: &'a str },
+    Close(&'a str),
+    Text(&'a str),
+}
+
+/// Renders the `<article class="day-desc">`
+/// sections of a puzzle page as Markdown, links
+/// to the site itself are made absolute with
+/// `base_url`.
+pub fn markdown(
+    html: &str,
+    base_url: &str,
+) -> Option<String> {
+    let base_url = base_url.trim_end_matches('/');
+    let articles = sections(
+        html,
+        "<article class=\"day-desc\">",
+        "</article>",
+    )
+    .map(|article| article_markdown(article, base_url))
+    .collect::<Vec<_>>();
+    (!articles.is_empty()).then(|| articles.join("\n"))
+}
+
+/// Splits html into tags and the text between
+/// them, the puzzle pages are simple enough that
+/// no real parser is needed.
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+        }
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = rest[start + 1..start + end]
+            .trim_end_matches('/');
+        let (name, attributes) =
+            tag.split_once(' ').unwrap_or((tag, ""));
+        tokens.push(match name.strip_prefix('/') {
+            Some(name) => Token::Close(name),
+            None => Token::Open { name, attributes },
+        });
+        rest = &rest[start + end + 1..];
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest));
+    }
+    tokens
+}
+
+fn attribute<'a>(
+    attributes: &'a str,
+    name: &str,
+) -> Option<&'a str> {
+    let (_, rest) =
+        attributes.split_once(&format!("{name}=\""))?;
+    Some(rest.split_once('"')?.0)
+}
+
+fn article_markdown(
+    article: &str,
+    base_url: &str,
+) -> String {
+    let mut markdown = String::new();
+    let mut in_pre = false;
+    let mut links = Vec::new();
+    for token in tokenize(article) {
+        match token {
+            Token::Text(text) if in_pre => {
+                markdown.push_str(&decode(text))
+            }
+            Token::Text(text) => {
+                let text = decode(text).replace('\n', " ");
+                if markdown.is_empty()
+                    || markdown.ends_with('\n')
+                {
+                    markdown.push_str(text.trim_start());
+                } else {
+                    markdown.push_str(&text);
+                }
+            }
+            Token::Open { name: "pre", .. } => {
+                markdown.push_str("```\n");
+                in_pre = true;
+            }
+            Token::Close("pre") => {
+                if !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("```\n\n");
+                in_pre = false;
+            }
+            // inline markup doesn't render inside
+            // code blocks
+            Token::Open { .. } | Token::Close(_)
+                if in_pre => {}
+            Token::Open { name: "h2", .. } => {
+                markdown.push_str("## ")
+            }
+            Token::Close("h2" | "p") => {
+                markdown.push_str("\n\n")
+            }
+            Token::Open { name: "li", .. } => {
+                markdown.push_str("- ")
+            }
+            Token::Close("li" | "ul")
+            | Token::Open { name: "br", .. } => {
+                markdown.push('\n')
+            }
+            Token::Open { name: "code", .. }
+            | Token::Close("code") => markdown.push('`'),
+            Token::Open { name: "em", .. }
+            | Token::Close("em") => markdown.push_str("**"),
+            Token::Open {
+                name: "a",
+                attributes,
+            } => {
+                let href = attribute(attributes, "href")
+                    .unwrap_or_default();
+                links.push(if href.starts_with('/') {
+                    format!("{base_url}{href}")
+                } else {
+                    href.to_string()
+                });
+                markdown.push('[');
+            }
+            Token::Close("a") => {
+                let href = links.pop().unwrap_or_default();
+                markdown.push_str(&format!("]({href})"));
+            }
+            Token::Open { .. } | Token::Close(_) => {}
+        }
+    }
+    bold_code(markdown.trim_end()) + "\n"
+}
+
+/// `<code><em>` is how answers are highlighted,
+/// the bold has to move outside the code span to
+/// render.
+fn bold_code(markdown: &str) -> String {
+    let mut result = String::new();
+    let mut rest = markdown;
+    while let Some(start) = rest.find("`**") {
+        let inner = &rest[start + 3..];
+        let Some(end) = inner
+            .find("**`")
+            .filter(|end| !inner[..*end].contains('`'))
+        else {
+            result.push_str(&rest[..start + 3]);
+            rest = inner;
+            continue;
+        };
+        result.push_str(&rest[..start]);
+        result
+            .push_str(&format!("**`{}`**", &inner[..end]));
+        rest = &inner[end + 3..];
+    }
+    result + rest
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    /// A puzzle page as the site serves it once
+    /// part 1 is solved, trimmed to the parts
+    /// that matter.
+    const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<head>
+<meta charset="utf-8"/>
+<title>Day 7 - Advent of Code 2025</title>
+<link rel="stylesheet" type="text/css" href="/static/style.css?31"/>
+</head><!--
+
+
+
+
+Oh, hello!  Funny seeing you here.
+-->
+<body>
+<header><div><h1 class="title-global"><a href="/">Advent of Code</a></h1><nav><ul><li><a href="/2025/about">[About]</a></li><li><a href="/2025/events">[Events]</a></li></ul></nav></div></header>
+<main>
+<article class="day-desc"><h2>--- Day 7: Laboratories ---</h2><p>You thank the cephalopods for the help and exit the trash compactor, finding yourself in the <a href="/2025/day/4">familiar</a> halls of a North Pole research wing.</p>
+<p>The <em>tachyon manifold</em> looks like this:</p>
+<pre><code>.......S.......
+.......^.......
+..&lt;&amp;&gt;..........
+</code></pre>
+<p>A beam is:</p>
+<ul>
+<li>split by every <code>^</code> it meets,</li>
+<li>never merged.</li>
+</ul>
+<p>In this example, a tachyon beam is split a total of <code><em>21</em></code> times.</p>
+<p>Analyze your manifold diagram. <em>How many times will the beam be split?</em></p>
+</article>
+<p>Your puzzle answer was <code>1507</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>With a <a href="https://en.wikipedia.org/wiki/Quantum_tunnelling" target="_blank">quantum</a> tachyon manifold,<br/>a single particle takes <em>every</em> path.</p>
+<p>In total, the particle ends up on <code><em>40</em></code> different timelines.</p>
+</article>
+<p>Answer: <form method="post" action="7/answer"><input type="hidden" name="level" value="2"/><input type="text" name="answer" autocomplete="off"/> <input type="submit" value="[Submit]"/></form></p>
+</main>
+</body>
+</html>"#;
+
+    const MARKDOWN: &str = r#"## --- Day 7: Laboratories ---
+
+You thank the cephalopods for the help and exit the trash compactor, finding yourself in the [familiar](https://adventofcode.com/2025/day/4) halls of a North Pole research wing.
+
+The **tachyon manifold** looks like this:
+
+```
+.......S.......
+.......^.......
+..<&>..........
+```
+
+A beam is:
+
+- split by every `^` it meets,
+- never merged.
+
+In this example, a tachyon beam is split a total of **`21`** times.
+
+Analyze your manifold diagram. **How many times will the beam be split?**
+
+## --- Part Two ---
+
+With a [quantum](https://en.wikipedia.org/wiki/Quantum_tunnelling) tachyon manifold,
+a single particle takes **every** path.
+
+In total, the particle ends up on **`40`** different timelines.
+"#;
+
+    #[test]
+    fn test_markdown() {
+        assert_eq!(
+            markdown(PAGE, "https://adventofcode.com/")
+                .as_deref(),
+            Some(MARKDOWN)
+        );
+    }
+
+    #[test]
+    fn test_markdown_part1_only() {
+        let page =
+            PAGE.split("<p>Your puzzle").next().unwrap();
+        let part1 =
+            markdown(page, "https://adventofcode.com")
+                .unwrap();
+        assert!(part1.ends_with("be split?**\n"));
+        assert!(!part1.contains("Part Two"));
+        assert_eq!(
+            markdown("<main>Please log in</main>", ""),
+            None
+        );
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let tokens =
+            tokenize("a<p class=\"x\">b<br/></p><c");
+        assert!(matches!(
+            tokens[..],
+            [
+                Token::Text("a"),
+                Token::Open {
+                    name: "p",
+                    attributes: "class=\"x\""
+                },
+                Token::Text("b"),
+                Token::Open {
+                    name: "br",
+                    attributes: ""
+                },
+                Token::Close("p"),
+                // an unterminated tag stays text
+                Token::Text("<c"),
+            ]
+        ));
+    }
+
+    #[rstest]
+    #[case("href=\"/2025\"", "href", Some("/2025"))]
+    #[case(
+        "target=\"_blank\" href=\"x\"",
+        "href",
+        Some("x")
+    )]
+    #[case("target=\"_blank\"", "href", None)]
+    #[case("href=\"unterminated", "href", None)]
+    fn test_attribute(
+        #[case] attributes: &str,
+        #[case] name: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        assert_eq!(attribute(attributes, name), expected);
+    }
+
+    #[rstest]
+    #[case("`**21**`", "**`21`**")]
+    #[case(
+        "a `**1**` and `**2**`",
+        "a **`1`** and **`2`**"
+    )]
+    #[case("`**` x `y**`", "`**` x `y**`")]
+    #[case("`code` **bold**", "`code` **bold**")]
+    fn test_bold_code(
+        #[case] markdown: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(bold_code(markdown), expected);
+    }
+}
@@ -0,0 +1,107 @@
+use aoc_core::Descriptor;
+
+/// Every solution the runner knows about,
+/// ordered by year and day.
+pub const SOLUTIONS: &[Descriptor] = &[
+    Descriptor::of::<day_01::Day>(),
+    Descriptor::of::<day_02::Day>(),
+    Descriptor::of::<day_03::Day>(),
+    Descriptor::of::<day_04::Day>(),
+    Descriptor::of::<day_05::Day>(),
+    Descriptor::of::<day_06::Day>(),
+    Descriptor::of::<day_07::Day>(),
+    Descriptor::of::<day_08::Day>(),
+    Descriptor::of::<day_09::Day>(),
+];
+
+/// A single part of a registered solution.
+#[derive(Clone, Copy, Debug)]
+pub struct Entry {
+    pub solution: &'static Descriptor,
+    pub part: u8,
+}
+
+impl Entry {
+    pub fn year(&self) -> u16 {
+        self.solution.year
+    }
+
+    pub fn day(&self) -> u8 {
+        self.solution.day
+    }
+}
+
+/// All registered parts, in order.
+pub fn all() -> impl Iterator<Item = Entry> {
+    SOLUTIONS.iter().flat_map(|solution| {
+        [1, 2].map(|part| Entry { solution, part })
+    })
+}
+
+/// The most recent year with a registered
+/// solution.
+pub fn latest_year() -> u16 {
+    SOLUTIONS
+        .iter()
+        .map(|solution| solution.year)
+        .max()
+        .expect("at least one solution is registered")
+}
+
+/// The registered parts for a given year and
+/// day, optionally narrowed down to one part.
+pub fn find(
+    year: u16,
+    day: u8,
+    part: Option<u8>,
+) -> impl Iterator<Item = Entry> {
+    all().filter(move |entry| {
+        entry.year() == year
+            && entry.day() == day
+            && part.is_none_or(|part| entry.part == part)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solutions_are_unique_and_sorted() {
+        let keys = all()
+            .map(|entry| {
+                (entry.year(), entry.day(), entry.part)
+            })
+            .collect::<Vec<_>>();
+        assert!(
+            keys.windows(2).all(|pair| pair[0] < pair[1])
+        );
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(2025, 7, None).count(), 2);
+        assert_eq!(find(2025, 7, Some(2)).count(), 1);
+        assert_eq!(find(2025, 42, None).count(), 0);
+        assert_eq!(find(2015, 7, None).count(), 0);
+    }
+
+    #[test]
+    fn test_latest_year() {
+        assert_eq!(latest_year(), 2025);
+    }
+
+    #[test]
+    fn test_find_runs_solution() -> miette::Result<()> {
+        let entry = find(2025, 3, Some(1)).next().unwrap();
+        let input = "987654321111111
+811111111111119
+234234234234278
+818181911112111";
+        assert_eq!(
+            "357",
+            entry.solution.run(input, entry.part)?.answer
+        );
+        Ok(())
+    }
+}
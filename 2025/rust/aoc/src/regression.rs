@@ -0,0 +1,207 @@
+use aoc_core::input::{InputError, Source};
+
+use crate::{answers::Answers, registry::Entry, runner};
+
+/// How a solution compares to the answer the
+/// server accepted for it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// still gives the accepted answer
+    Verified,
+    /// no answer was accepted yet
+    Unpinned,
+    /// the puzzle input isn't downloaded
+    MissingInput,
+    Drifted {
+        expected: String,
+        actual: String,
+    },
+}
+
+/// Runs the part on the input from `source` and
+/// compares the result to its accepted answer.
+pub fn verify(
+    answers: &Answers,
+    entry: &Entry,
+    source: &Source,
+) -> miette::Result<Status> {
+    let Some(expected) = answers.accepted(
+        entry.year(),
+        entry.day(),
+        entry.part,
+    ) else {
+        return Ok(Status::Unpinned);
+    };
+    let input = match source.read() {
+        Ok(input) => input,
+        Err(InputError::Missing { .. }) => {
+            return Ok(Status::MissingInput);
+        }
+        Err(error) => return Err(error.into()),
+    };
+    let actual = runner::run(entry, &input)?.answer;
+    Ok(if actual == expected {
+        Status::Verified
+    } else {
+        Status::Drifted {
+            expected: expected.to_string(),
+            actual,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        answers::{Attempt, Verdict},
+        registry,
+    };
+    use std::{fs, path::PathBuf};
+
+    fn answers() -> Answers {
+        let mut answers = Answers::default();
+        for (part, answer, verdict) in [
+            (1, "98", Verdict::Correct),
+            (2, "1", Verdict::TooLow),
+            (2, "987654321111", Verdict::Correct),
+        ] {
+            answers.record(Attempt::new(
+                2025, 3, part, answer, verdict,
+            ));
+        }
+        answers
+    }
+
+    fn input_file(name: &str, input: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "aoc-regression-{}-{name}.txt",
+            std::process::id()
+        ));
+        fs::write(&path, input).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_verify() -> miette::Result<()> {
+        let path = input_file("verify", "987654321111111");
+        let source = Source::File(path.clone());
+        let answers = answers();
+        let part1 = registry::find(2025, 3, Some(1))
+            .next()
+            .unwrap();
+        let part2 = registry::find(2025, 3, Some(2))
+            .next()
+            .unwrap();
+        let other = registry::find(2025, 4, Some(1))
+            .next()
+            .unwrap();
+
+        let results = [
+            verify(&answers, &part1, &source),
+            verify(&answers, &part2, &source),
+            verify(&answers, &other, &source),
+        ];
+        fs::remove_file(path).unwrap();
+
+        let [part1, part2, other] = results;
+        assert_eq!(Status::Verified, part1?);
+        assert_eq!(Status::Verified, part2?);
+        assert_eq!(Status::Unpinned, other?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_drifted() -> miette::Result<()> {
+        let path = input_file("drifted", "111111111111119");
+        let entry = registry::find(2025, 3, Some(1))
+            .next()
+            .unwrap();
+        let status = verify(
+            &answers(),
+            &entry,
+            &Source::File(path.clone()),
+        );
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(
+            Status::Drifted {
+                expected: "98".to_string(),
+                actual: "19".to_string()
+            },
+            status?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_missing_input() -> miette::Result<()> {
+        let entry = registry::find(2025, 3, Some(1))
+            .next()
+            .unwrap();
+        let source = Source::File(
+            std::env::temp_dir()
+                .join("aoc-no-such-input.txt"),
+        );
+        assert_eq!(
+            Status::MissingInput,
+            verify(&answers(), &entry, &source)?
+        );
+        Ok(())
+    }
+
+    /// Every accepted answer in `answers.toml`
+    /// still comes out of its solution, days
+    /// without a downloaded input are
+    /// skipped. Parts without an accepted answer
+    /// can't be checked, so they are listed
+    /// rather than passing silently.
+    #[test]
+    fn test_accepted_answers() -> miette::Result<()> {
+        let path = Answers::default_path();
+        let answers = Answers::load(&path)?;
+        let mut verified = 0;
+        let mut unpinned = Vec::new();
+        let mut drifted = Vec::new();
+        for entry in registry::all() {
+            let status = verify(
+                &answers,
+                &entry,
+                &runner::input_source(&entry),
+            )?;
+            let name = format!(
+                "{} day {:02} part {}",
+                entry.year(),
+                entry.day(),
+                entry.part
+            );
+            match status {
+                Status::Drifted { expected, actual } => {
+                    drifted.push(format!(
+                        "{name}: expected {expected}, got {actual}"
+                    ))
+                }
+                Status::MissingInput => {
+                    eprintln!("skipping {name}, no input")
+                }
+                Status::Unpinned => unpinned.push(name),
+                Status::Verified => verified += 1,
+            }
+        }
+        if !unpinned.is_empty() {
+            eprintln!(
+                "{} has no accepted answer for {}, \
+                 `just submit` records them",
+                path.display(),
+                unpinned.join(", ")
+            );
+        }
+        eprintln!("verified {verified} accepted answers");
+        assert!(
+            drifted.is_empty(),
+            "{}",
+            drifted.join("\n")
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,346 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use serde::Serialize;
+
+use crate::{
+    bench::{History, Record, format_time},
+    registry::{self, SOLUTIONS},
+};
+
+/// The latest median of a benchmark.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Row {
+    pub year: u16,
+    pub day: u8,
+    pub title: String,
+    /// e.g. `part1`, or `parse` and `solve/part1`
+    /// for benchmarks of a step
+    pub benchmark: String,
+    pub median_ns: f64,
+    pub commit: String,
+}
+
+impl Row {
+    /// Whether this times a whole part rather
+    /// than a step of one, only those add up
+    /// to the totals.
+    pub fn is_part(&self) -> bool {
+        matches!(
+            self.benchmark.as_str(),
+            "part1" | "part2"
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct YearTotal {
+    pub year: u16,
+    pub median_ns: f64,
+}
+
+/// Median times of every benchmarked day and
+/// part, for the README.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Report {
+    pub rows: Vec<Row>,
+    pub totals: Vec<YearTotal>,
+    /// the slowest parts, slowest first
+    pub slowest: Vec<Row>,
+}
+
+/// The day of a bench target like `day_05_bench`.
+fn bench_day(bench: &str) -> Option<u8> {
+    bench
+        .strip_prefix("day_")?
+        .strip_suffix("_bench")?
+        .parse()
+        .ok()
+}
+
+impl Report {
+    /// Builds the report from the latest record
+    /// of every benchmark, keeping the
+    /// `slowest` parts as highlights.
+    pub fn new(history: &History, slowest: usize) -> Self {
+        let mut latest: BTreeMap<(u16, u8, &str), &Record> =
+            BTreeMap::new();
+        for record in &history.records {
+            let Some(day) =
+                bench_day(&record.measurement.bench)
+            else {
+                continue;
+            };
+            // bench targets don't carry a year, the
+            // workspace only holds one
+            let year = SOLUTIONS
+                .iter()
+                .find(|solution| solution.day == day)
+                .map_or_else(
+                    registry::latest_year,
+                    |solution| solution.year,
+                );
+            latest.insert(
+                (year, day, &record.measurement.name),
+                record,
+            );
+        }
+        let rows = latest
+            .into_iter()
+            .map(|((year, day, benchmark), record)| Row {
+                year,
+                day,
+                title: registry::find(year, day, None)
+                    .next()
+                    .map(|entry| entry.solution.title)
+                    .unwrap_or_default()
+                    .to_string(),
+                benchmark: benchmark.to_string(),
+                median_ns: record.measurement.median_ns,
+                commit: record.commit.clone(),
+            })
+            .collect::<Vec<_>>();
+
+        let mut totals: BTreeMap<u16, f64> =
+            BTreeMap::new();
+        for row in rows.iter().filter(|row| row.is_part()) {
+            *totals.entry(row.year).or_default() +=
+                row.median_ns;
+        }
+        let mut parts = rows
+            .iter()
+            .filter(|row| row.is_part())
+            .cloned()
+            .collect::<Vec<_>>();
+        parts.sort_by(|a, b| {
+            b.median_ns.total_cmp(&a.median_ns)
+        });
+        parts.truncate(slowest);
+
+        Report {
+            rows,
+            totals: totals
+                .into_iter()
+                .map(|(year, median_ns)| YearTotal {
+                    year,
+                    median_ns,
+                })
+                .collect(),
+            slowest: parts,
+        }
+    }
+
+    fn total(&self, year: u16) -> f64 {
+        self.totals
+            .iter()
+            .find(|total| total.year == year)
+            .map_or(0.0, |total| total.median_ns)
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+        markdown.push_str(
+            "| Year | Day | Title | Benchmark | Median |\n\
+             |---:|---:|---|---|---:|\n",
+        );
+        for row in &self.rows {
+            let _ = writeln!(
+                markdown,
+                "| {} | {} | {} | {} | {} |",
+                row.year,
+                row.day,
+                row.title,
+                row.benchmark,
+                format_time(row.median_ns)
+            );
+        }
+
+        markdown
+            .push_str("\n| Year | Total |\n|---:|---:|\n");
+        for total in &self.totals {
+            let _ = writeln!(
+                markdown,
+                "| {} | {} |",
+                total.year,
+                format_time(total.median_ns)
+            );
+        }
+
+        if !self.slowest.is_empty() {
+            markdown.push_str("\nSlowest solutions:\n\n");
+        }
+        for (rank, row) in (1..).zip(&self.slowest) {
+            let _ = writeln!(
+                markdown,
+                "{rank}. **{} day {} {}** ({}): {}, {:.0}% of {}",
+                row.year,
+                row.day,
+                row.benchmark,
+                row.title,
+                format_time(row.median_ns),
+                row.median_ns / self.total(row.year)
+                    * 100.0,
+                row.year
+            );
+        }
+        markdown
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self)
+            .expect("report should serialize to json")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bench::Measurement;
+
+    fn history() -> History {
+        let measurement =
+            |bench: &str, name: &str, median_ns| {
+                Measurement {
+                    bench: bench.to_string(),
+                    name: name.to_string(),
+                    fastest_ns: median_ns,
+                    slowest_ns: median_ns,
+                    median_ns,
+                    mean_ns: median_ns,
+                    samples: 100,
+                    iters: 100,
+                }
+            };
+        let mut history = History::default();
+        history.record(
+            "aaaaaaa",
+            1,
+            [
+                measurement("day_08_bench", "part1", 1e9),
+                measurement("day_05_bench", "part1", 100.0),
+            ],
+        );
+        history.record(
+            "bbbbbbb",
+            2,
+            [
+                measurement(
+                    "day_08_bench",
+                    "part1",
+                    273.4e6,
+                ),
+                // the steps `aoc_core::benches!`
+                // times besides the parts
+                measurement("day_08_bench", "parse", 30e3),
+                measurement(
+                    "day_08_bench",
+                    "solve/part1",
+                    250e6,
+                ),
+                measurement(
+                    "day_05_bench",
+                    "part2",
+                    10.89e3,
+                ),
+                measurement("criterion", "part1", 1.0),
+            ],
+        );
+        history
+    }
+
+    #[test]
+    fn test_report() {
+        let report = Report::new(&history(), 2);
+        let rows = report
+            .rows
+            .iter()
+            .map(|row| {
+                (
+                    row.day,
+                    row.benchmark.as_str(),
+                    row.median_ns,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            [
+                (5, "part1", 100.0),
+                (5, "part2", 10.89e3),
+                (8, "parse", 30e3),
+                (8, "part1", 273.4e6),
+                (8, "solve/part1", 250e6),
+            ]
+        );
+        assert_eq!(report.rows[3].title, "Playground");
+        assert_eq!(report.rows[3].commit, "bbbbbbb");
+        assert_eq!(
+            report.totals,
+            [YearTotal {
+                year: 2025,
+                median_ns: 100.0 + 10.89e3 + 273.4e6
+            }]
+        );
+        assert_eq!(
+            report
+                .slowest
+                .iter()
+                .map(|row| (
+                    row.day,
+                    row.benchmark.as_str()
+                ))
+                .collect::<Vec<_>>(),
+            [(8, "part1"), (5, "part2")]
+        );
+    }
+
+    #[test]
+    fn test_report_leaves_out_steps() {
+        let report = Report::new(&history(), usize::MAX);
+        let steps = ["parse", "solve/part1"];
+        assert!(
+            report
+                .rows
+                .iter()
+                .filter(|row| steps
+                    .contains(&row.benchmark.as_str()))
+                .all(|row| !row.is_part())
+        );
+        assert_eq!(
+            report
+                .slowest
+                .iter()
+                .map(|row| (
+                    row.day,
+                    row.benchmark.as_str()
+                ))
+                .collect::<Vec<_>>(),
+            [(8, "part1"), (5, "part2"), (5, "part1")]
+        );
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let markdown =
+            Report::new(&history(), 1).to_markdown();
+        assert!(markdown.contains(
+            "| 2025 | 8 | Playground | part1 | 273.4 ms |"
+        ));
+        assert!(markdown.contains("| 2025 | 273.4 ms |"));
+        assert!(markdown.contains(
+            "1. **2025 day 8 part1** (Playground): 273.4 ms, 100% of 2025"
+        ));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json = Report::new(&history(), 1).to_json();
+        let value: serde_json::Value =
+            serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            value["rows"].as_array().unwrap().len(),
+            5
+        );
+        assert_eq!(value["totals"][0]["year"], 2025);
+        assert_eq!(value["slowest"][0]["day"], 8);
+    }
+}
@@ -0,0 +1,65 @@
+use std::path::{Path, PathBuf};
+
+use aoc_core::{Outcome, input::Source};
+use miette::Context;
+
+use crate::registry::Entry;
+
+/// Directory containing the `day-NN` crates.
+pub fn workspace_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect(
+            "aoc crate should live inside the workspace",
+        )
+        .to_path_buf()
+}
+
+/// The crate directory of a day, e.g. `day-07`.
+pub fn day_dir(day: u8) -> PathBuf {
+    workspace_dir().join(format!("day-{day:02}"))
+}
+
+/// Where the input for a day comes from when
+/// none is passed explicitly.
+pub fn input_source(entry: &Entry) -> Source {
+    Source::from_env(None, &day_dir(entry.day()))
+}
+
+/// Runs the part on `input`.
+pub fn run(
+    entry: &Entry,
+    input: &str,
+) -> miette::Result<Outcome> {
+    entry.solution.run(input, entry.part).wrap_err_with(
+        || {
+            format!(
+                "process {} day {:02} part {}",
+                entry.year(),
+                entry.day(),
+                entry.part
+            )
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry;
+
+    #[test]
+    fn test_day_dir() {
+        assert!(day_dir(7).ends_with("day-07"));
+    }
+
+    #[test]
+    fn test_run() -> miette::Result<()> {
+        let entry = registry::find(2025, 3, Some(1))
+            .next()
+            .unwrap();
+        let outcome = run(&entry, "987654321111111")?;
+        assert_eq!(outcome.answer, "98");
+        Ok(())
+    }
+}
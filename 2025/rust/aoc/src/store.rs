@@ -0,0 +1,83 @@
+//! The toml files the runner keeps at the
+//! workspace root.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use miette::Diagnostic;
+use serde::{Serialize, de::DeserializeOwned};
+use thiserror::Error;
+
+#[derive(Error, Diagnostic, Debug)]
+pub enum StoreError {
+    #[error("failed to read {}", path.display())]
+    Read { path: PathBuf, source: io::Error },
+    #[error("failed to parse {}", path.display())]
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    #[error("failed to write {}", path.display())]
+    Write { path: PathBuf, source: io::Error },
+    #[error("failed to serialize {}", path.display())]
+    Serialize {
+        path: PathBuf,
+        source: toml::ser::Error,
+    },
+}
+
+/// Reads a toml file, a missing file counts as
+/// empty.
+pub fn load<T: DeserializeOwned + Default>(
+    path: &Path,
+) -> Result<T, StoreError> {
+    match fs::read_to_string(path) {
+        Ok(contents) => {
+            toml::from_str(&contents).map_err(|source| {
+                StoreError::Parse {
+                    path: path.to_path_buf(),
+                    source,
+                }
+            })
+        }
+        Err(error)
+            if error.kind() == io::ErrorKind::NotFound =>
+        {
+            Ok(T::default())
+        }
+        Err(source) => Err(StoreError::Read {
+            path: path.to_path_buf(),
+            source,
+        }),
+    }
+}
+
+pub fn save<T: Serialize>(
+    path: &Path,
+    value: &T,
+) -> Result<(), StoreError> {
+    let contents =
+        toml::to_string(value).map_err(|source| {
+            StoreError::Serialize {
+                path: path.to_path_buf(),
+                source,
+            }
+        })?;
+    fs::write(path, contents).map_err(|source| {
+        StoreError::Write {
+            path: path.to_path_buf(),
+            source,
+        }
+    })
+}
+
+/// Seconds since the unix epoch, the timestamp
+/// format of the stored files.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
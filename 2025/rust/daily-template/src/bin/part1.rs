@@ -5,8 +5,10 @@ use miette::Context;
 fn main() -> miette::Result<()> {
     tracing_subscriber::fmt::init();
 
-    let file = include_str!("../../input1.txt");
-    let result = process(file).context("process part 1")?;
+    let file =
+        aoc_core::input::load(env!("CARGO_MANIFEST_DIR"))?;
+    let result =
+        process(&file).context("process part 1")?;
     println!("{}", result);
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,12 @@
+use aoc_core::parse::ParseError;
+
+/// Why the puzzle can't be solved, give every
+/// other way the input can be wrong a variant
+/// with a code and help.
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum PuzzleError {
+    /// Input that doesn't have the expected shape.
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Parse(#[from] ParseError),
+}
@@ -1,2 +1,30 @@
+use aoc_core::Solution;
+
+pub use crate::error::PuzzleError;
+
+mod error;
+pub mod parse;
 pub mod part1;
 pub mod part2;
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = {{year}};
+    const DAY: u8 = {{day_number}};
+    const TITLE: &'static str = "";
+
+    type Parsed<'a> = parse::Parsed<'a>;
+
+    fn parse(input: &str) -> miette::Result<Self::Parsed<'_>> {
+        Ok(parse::parse(input)?)
+    }
+
+    fn part1(input: &Self::Parsed<'_>) -> miette::Result<String> {
+        part1::solve(input)
+    }
+
+    fn part2(input: &Self::Parsed<'_>) -> miette::Result<String> {
+        part2::solve(input)
+    }
+}
@@ -0,0 +1,25 @@
+use crate::PuzzleError;
+
+/// What both parts work on, change it to whatever
+/// the puzzle needs.
+pub type Parsed<'a> = Vec<&'a str>;
+
+#[tracing::instrument]
+pub fn parse(
+    input: &str,
+) -> Result<Parsed<'_>, PuzzleError> {
+    Ok(input.lines().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(include_str!("../examples/part1-1.txt"))]
+    fn test_parse(#[case] input: &str) -> miette::Result<()> {
+        assert_eq!(parse(input)?.len(), input.lines().count());
+        Ok(())
+    }
+}
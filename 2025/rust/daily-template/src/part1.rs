@@ -1,18 +1,21 @@
+use crate::parse::{Parsed, parse};
+
 #[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String> {
-    todo!("day 01 - part 1");
+pub fn process(input: &str) -> miette::Result<String> {
+    let parsed = parse(input)?;
+    solve(&parsed)
+}
+
+pub fn solve(_parsed: &Parsed<'_>) -> miette::Result<String> {
+    todo!("day {{day}} - part 1");
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    #[ignore]
-    fn test_process() -> miette::Result<()> {
-        todo!("haven't built test yet");
-        let input = "";
-        assert_eq!("", process(input)?);
-        Ok(())
-    }
+    // `just examples {{day}}` fills in the example
+    // files, further examples like
+    // `examples/part1-2.txt` are picked up too
+    aoc_core::example_tests!(part1);
 }
@@ -0,0 +1,11 @@
+use aoc_core::parse::ParseError;
+
+/// Why the rotations for the safe's dial can't be
+/// followed.
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum DialError {
+    /// A line that isn't a rotation like `L68`.
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Parse(#[from] ParseError),
+}
@@ -1,2 +1,36 @@
+use aoc_core::Solution;
+
+pub use crate::error::DialError;
+use crate::part1::Instruction;
+
+mod error;
 pub mod part1;
 pub mod part2;
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2025;
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Secret Entrance";
+
+    type Parsed<'a> = Vec<Instruction>;
+
+    fn parse(
+        input: &str,
+    ) -> miette::Result<Vec<Instruction>> {
+        Ok(part1::parse(input)?)
+    }
+
+    fn part1(
+        instructions: &Vec<Instruction>,
+    ) -> miette::Result<String> {
+        part1::solve(instructions)
+    }
+
+    fn part2(
+        instructions: &Vec<Instruction>,
+    ) -> miette::Result<String> {
+        part2::solve(instructions)
+    }
+}
@@ -1,75 +1,234 @@
+use std::str::FromStr;
+
+use aoc_core::parse::{
+    IResult, Span, finish, lines, unsigned,
+};
+use nom::{
+    Parser, character::complete::one_of, error::context,
+};
+
+use crate::DialError;
+
 #[tracing::instrument]
-pub fn process(_input: &str) -> miette::Result<String> {
-    let password = parse_sequence("input1.txt");
+pub fn process(input: &str) -> miette::Result<String> {
+    let instructions = parse(input)?;
+    solve(&instructions)
+}
+
+pub fn parse(
+    input: &str,
+) -> Result<Vec<Instruction>, DialError> {
+    Ok(finish(input, lines(Instruction::parse))?)
+}
+
+pub fn solve(
+    instructions: &[Instruction],
+) -> miette::Result<String> {
+    let mut dial = Dial::default();
+    let password = instructions
+        .iter()
+        .filter(|&&instruction| {
+            dial.rotate(instruction);
+            dial.position() == 0
+        })
+        .count();
     Ok(password.to_string())
 }
 
 const DIAL_SIZE: u32 = 100;
 const DIAL_STARTING_POSITION: u32 = 50;
 
-fn rotate_right(dial: u32, distance: u32) -> u32 {
-    let dist = distance % DIAL_SIZE;
-    (dial + dist) % DIAL_SIZE
+/// The safe's dial, pointing at one of the
+/// numbers `0..DIAL_SIZE`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Dial {
+    position: u32,
 }
 
-fn rotate_left(dial: u32, distance: u32) -> u32 {
-    let dist = distance % DIAL_SIZE;
-    rotate_right(dial, DIAL_SIZE - dist)
+impl Dial {
+    pub fn new(position: u32) -> Self {
+        Dial {
+            position: position % DIAL_SIZE,
+        }
+    }
+
+    pub fn position(&self) -> u32 {
+        self.position
+    }
+
+    /// Turns the dial and returns how many times
+    /// it pointed at 0 along the way,
+    /// including where it ends up but not
+    /// where it started.
+    pub fn rotate(
+        &mut self,
+        instruction: Instruction,
+    ) -> u32 {
+        let Instruction {
+            direction,
+            distance,
+        } = instruction;
+        // measure everything as a distance travelled
+        // towards 0, so both directions count alike
+        let towards_zero = match direction {
+            Direction::Left => {
+                (DIAL_SIZE - self.position) % DIAL_SIZE
+            }
+            Direction::Right => self.position,
+        };
+        // split off the full turns first, so huge
+        // distances can't overflow
+        let clicks = distance / DIAL_SIZE
+            + (towards_zero + distance % DIAL_SIZE)
+                / DIAL_SIZE;
+
+        let distance = distance % DIAL_SIZE;
+        self.position = match direction {
+            Direction::Left => {
+                (self.position + DIAL_SIZE - distance)
+                    % DIAL_SIZE
+            }
+            Direction::Right => {
+                (self.position + distance) % DIAL_SIZE
+            }
+        };
+        clicks
+    }
 }
 
-fn parse_instruction(dial: u32, instruction: &str) -> u32 {
-    let (direction, distance) = instruction.split_at(1);
-    let dist: u32 = distance.parse().unwrap_or_default();
-    match direction {
-        "L" => rotate_left(dial, dist),
-        "R" => rotate_right(dial, dist),
-        _ => dial,
+impl Default for Dial {
+    fn default() -> Self {
+        Dial::new(DIAL_STARTING_POSITION)
     }
 }
 
-fn parse_sequence(file_path: &str) -> u32 {
-    let contents = std::fs::read_to_string(file_path)
-        .expect("Should have been able to read the file");
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Direction {
+    Left,
+    Right,
+}
 
-    let mut dial = DIAL_STARTING_POSITION;
-    let mut zero_counter = 0;
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Instruction {
+    pub direction: Direction,
+    pub distance: u32,
+}
 
-    for instruction in contents.lines().into_iter() {
-        dial = parse_instruction(dial, instruction);
-        if dial == 0 {zero_counter += 1}
+impl Instruction {
+    /// An instruction like `L68`.
+    pub fn parse(input: Span<'_>) -> IResult<'_, Self> {
+        (
+            context("a direction, `L` or `R`", one_of("LR"))
+                .map(|direction| match direction {
+                    'L' => Direction::Left,
+                    _ => Direction::Right,
+                }),
+            unsigned,
+        )
+            .map(|(direction, distance)| Instruction {
+                direction,
+                distance,
+            })
+            .parse(input)
     }
+}
+
+impl FromStr for Instruction {
+    type Err = DialError;
 
-    zero_counter
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        Ok(finish(line, Instruction::parse)?)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use rstest::rstest;
+
     use super::*;
 
-    #[test]
-    fn test_rotate_right() {
-        assert_eq!(rotate_right(10, 20), 30);
-        assert_eq!(rotate_right(90, 20), 10);
+    fn turn(
+        position: u32,
+        direction: Direction,
+        distance: u32,
+    ) -> u32 {
+        let mut dial = Dial::new(position);
+        dial.rotate(Instruction {
+            direction,
+            distance,
+        });
+        dial.position()
     }
 
     #[test]
-    fn test_rotate_left() {
-        assert_eq!(rotate_left(10, 20), 90);
-        assert_eq!(rotate_left(90, 20), 70);
+    fn test_rotate_right() {
+        assert_eq!(turn(10, Direction::Right, 20), 30);
+        assert_eq!(turn(90, Direction::Right, 20), 10);
+        assert_eq!(turn(90, Direction::Right, 120), 10);
     }
 
     #[test]
-    fn test_parse_instruction() {
-        assert_eq!(parse_instruction(50, "R10"), 60);
-        assert_eq!(parse_instruction(50, "L10"), 40);
+    fn test_rotate_left() {
+        assert_eq!(turn(10, Direction::Left, 20), 90);
+        assert_eq!(turn(90, Direction::Left, 20), 70);
+        assert_eq!(turn(0, Direction::Left, 5), 95);
     }
 
     #[test]
-    fn test_process() -> miette::Result<()> {
+    fn test_parse_instruction() -> miette::Result<()> {
+        assert_eq!(
+            "R10".parse::<Instruction>()?,
+            Instruction {
+                direction: Direction::Right,
+                distance: 10
+            }
+        );
         assert_eq!(
-            parse_sequence("example.txt"),
-            3
+            "L1000".parse::<Instruction>()?,
+            Instruction {
+                direction: Direction::Left,
+                distance: 1000
+            }
+        );
+        assert!("".parse::<Instruction>().is_err());
+        assert!("U10".parse::<Instruction>().is_err());
+        assert!("L".parse::<Instruction>().is_err());
+        assert!("R10x".parse::<Instruction>().is_err());
+        assert!(
+            "R99999999999".parse::<Instruction>().is_err()
         );
         Ok(())
     }
+
+    #[test]
+    fn test_huge_distance() {
+        let mut dial = Dial::new(99);
+        let clicks = dial.rotate(Instruction {
+            direction: Direction::Right,
+            distance: u32::MAX,
+        });
+        assert_eq!(clicks, u32::MAX / 100 + 1);
+        assert_eq!(dial.position(), 94);
+    }
+
+    #[rstest]
+    #[case::empty("")]
+    #[case::truncated("L68\nL30\nR")]
+    #[case::blank_line("L68\n\nL30")]
+    #[case::garbage("L68\n\u{1F512}\n")]
+    #[case::sign("R-5")]
+    fn test_malformed(#[case] input: &str) {
+        assert!(matches!(
+            parse(input),
+            Err(DialError::Parse(_))
+        ));
+        for result in [process(input), crate::part2::process(input)] {
+            assert!(matches!(
+                result.unwrap_err().downcast(),
+                Ok(DialError::Parse(_))
+            ));
+        }
+    }
+
+    aoc_core::example_tests!(part1);
 }
@@ -0,0 +1,32 @@
+use aoc_core::parse::ParseError;
+
+/// Why the product id ranges can't be checked.
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum GiftShopError {
+    /// Input that isn't a list of ranges like
+    /// `11-22`.
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Parse(#[from] ParseError),
+    #[error(
+        "the range {start}-{end} ends before it starts"
+    )]
+    #[diagnostic(
+        code(day_02::reversed),
+        help(
+            "ranges go from the first id to the last, e.g. `11-22`"
+        )
+    )]
+    Reversed { start: u64, end: u64 },
+    #[error(
+        "the invalid ids add up to more than {}",
+        u128::MAX
+    )]
+    #[diagnostic(
+        code(day_02::overflow),
+        help(
+            "the ranges are far larger than any puzzle input"
+        )
+    )]
+    Overflow,
+}
@@ -0,0 +1,159 @@
+//! Summing the invalid ids in a range without
+//! visiting every id in it.
+//!
+//! An id of `length` digits made of a block of
+//! `period` digits repeated is that block times
+//! the repunit-like `10…010…01`, e.g. `123123` is
+//! `123 * 1001`. So the invalid ids of one length
+//! and period are the multiples of that number by
+//! the blocks of `period` digits, an arithmetic
+//! series that can be summed directly.
+
+use std::ops::RangeInclusive;
+
+/// The most digits a `u64` has.
+const MAX_LENGTH: u32 = 20;
+
+/// The sum of the ids in `range` made of some
+/// block of digits repeated exactly twice, like
+/// `6464`.
+pub fn sum_doubled(range: &RangeInclusive<u64>) -> u128 {
+    (2..=MAX_LENGTH)
+        .step_by(2)
+        .map(|length| {
+            sum_periodic(range, length, length / 2)
+        })
+        .sum()
+}
+
+/// The sum of the ids in `range` made of some
+/// block of digits repeated at least twice, like
+/// `6464` or `111`.
+///
+/// An id repeating a block of `a` digits and one
+/// of `b` digits also repeats a block of
+/// `gcd(a, b)` digits, so summing every period
+/// would count ids more than once. Inclusion–
+/// exclusion over the divisors of the length
+/// counts each id once: the ids whose shortest
+/// period is the whole length are left out by
+/// the Möbius function.
+pub fn sum_repeated(range: &RangeInclusive<u64>) -> u128 {
+    (1..=MAX_LENGTH)
+        .map(|length| {
+            (1..length)
+                .filter(|period| {
+                    length.is_multiple_of(*period)
+                })
+                .map(|period| {
+                    -mobius(length / period)
+                        * sum_periodic(
+                            range, length, period,
+                        ) as i128
+                })
+                .sum::<i128>() as u128
+        })
+        .sum()
+}
+
+/// The sum of the ids in `range` with `length`
+/// digits that repeat a block of `period` digits,
+/// where `period` divides `length`.
+fn sum_periodic(
+    range: &RangeInclusive<u64>,
+    length: u32,
+    period: u32,
+) -> u128 {
+    let (from, to) = (
+        u128::from(*range.start()),
+        u128::from(*range.end()),
+    );
+    // 10^length - 1 is `length` nines, dividing by
+    // `period` nines leaves `10…010…01`
+    let multiplier =
+        (10u128.pow(length) - 1) / (10u128.pow(period) - 1);
+    let lowest = 10u128
+        .pow(period - 1)
+        .max(from.div_ceil(multiplier));
+    let highest =
+        (10u128.pow(period) - 1).min(to / multiplier);
+    if lowest > highest {
+        return 0;
+    }
+    multiplier * (lowest + highest) * (highest - lowest + 1)
+        / 2
+}
+
+/// The Möbius function, `0` when `n` has a square
+/// factor and otherwise `-1` or `1` for an odd or
+/// even number of prime factors.
+fn mobius(mut n: u32) -> i128 {
+    let mut result = 1;
+    let mut factor = 2;
+    while factor * factor <= n {
+        if n.is_multiple_of(factor) {
+            n /= factor;
+            if n.is_multiple_of(factor) {
+                return 0;
+            }
+            result = -result;
+        }
+        factor += 1;
+    }
+    if n > 1 { -result } else { result }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case(11..=22, 33)]
+    #[case(95..=115, 99)]
+    #[case(1188511880..=1188511890, 1188511885)]
+    #[case(1698522..=1698528, 0)]
+    fn test_sum_doubled(
+        #[case] range: RangeInclusive<u64>,
+        #[case] expected: u128,
+    ) {
+        assert_eq!(sum_doubled(&range), expected);
+    }
+
+    #[rstest]
+    #[case(11..=22, 33)]
+    #[case(95..=115, 99 + 111)]
+    #[case(998..=1012, 999 + 1010)]
+    #[case(565653..=565659, 565656)]
+    #[case(2121212118..=2121212124, 2121212121)]
+    fn test_sum_repeated(
+        #[case] range: RangeInclusive<u64>,
+        #[case] expected: u128,
+    ) {
+        assert_eq!(sum_repeated(&range), expected);
+    }
+
+    #[test]
+    fn test_whole_domain() {
+        // nothing overflows anywhere in the u64s
+        let range = 0..=u64::MAX;
+        assert!(sum_repeated(&range) > sum_doubled(&range));
+        assert_eq!(
+            sum_repeated(&(1..=1000)),
+            (1..=9).map(|digit| digit * (11 + 111)).sum()
+        );
+    }
+
+    #[rstest]
+    #[case(1, 1)]
+    #[case(2, -1)]
+    #[case(4, 0)]
+    #[case(6, 1)]
+    #[case(12, 0)]
+    #[case(19, -1)]
+    #[case(20, 0)]
+    fn test_mobius(#[case] n: u32, #[case] expected: i128) {
+        assert_eq!(mobius(n), expected);
+    }
+}
@@ -1,2 +1,38 @@
+use std::ops::RangeInclusive;
+
+use aoc_core::Solution;
+
+pub use crate::error::GiftShopError;
+
+mod error;
+pub mod invalid_ids;
 pub mod part1;
 pub mod part2;
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2025;
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Gift Shop";
+
+    type Parsed<'a> = Vec<RangeInclusive<u64>>;
+
+    fn parse(
+        input: &str,
+    ) -> miette::Result<Vec<RangeInclusive<u64>>> {
+        Ok(part1::parse_ranges(input)?)
+    }
+
+    fn part1(
+        ranges: &Vec<RangeInclusive<u64>>,
+    ) -> miette::Result<String> {
+        part1::solve(ranges)
+    }
+
+    fn part2(
+        ranges: &Vec<RangeInclusive<u64>>,
+    ) -> miette::Result<String> {
+        part2::solve(ranges)
+    }
+}
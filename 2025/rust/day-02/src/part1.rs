@@ -1,86 +1,257 @@
+use std::ops::RangeInclusive;
+
+use aoc_core::parse::{comma_list, finish, range};
+
+use crate::{GiftShopError, invalid_ids::sum_doubled};
+
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String> {
+    let ranges = parse_ranges(input)?;
+    solve(&ranges)
+}
+
+pub fn solve(
+    ranges: &[RangeInclusive<u64>],
+) -> miette::Result<String> {
     Ok(
-        list_product_ids(input.trim())
-            .iter()
-            .filter_map(|id| find_repeating_digits(id))
-            .map(|digits| digits.parse::<u64>().unwrap())
-            .sum::<u64>()
-            .to_string()
+        checked_total(ranges.iter().map(sum_doubled))?
+            .to_string(),
     )
 }
 
-fn list_product_ids(input: &str) -> Vec<String> {
-    input
-        .split(',')
-        .filter_map(|part| {
-            let (from_str, to_str) = part.trim().split_once('-')?;
-            let from = from_str.trim().parse::<u64>().ok()?;
-            let to = to_str.trim().parse::<u64>().ok()?;
-            Some(from..=to)
-        })
-        .flat_map(|range| range.map(|n| n.to_string()))
-        .collect()
+pub fn parse_ranges(
+    input: &str,
+) -> Result<Vec<RangeInclusive<u64>>, GiftShopError> {
+    let ranges = finish(input, comma_list(range))?;
+    if let Some(range) =
+        ranges.iter().find(|range| range.is_empty())
+    {
+        return Err(GiftShopError::Reversed {
+            start: *range.start(),
+            end: *range.end(),
+        });
+    }
+    Ok(ranges)
 }
 
-fn find_repeating_digits(input: &str) -> Option<&str> {
-    let full_length = input.len();
-    if full_length % 2 == 1 {return None}
-    let window_size = full_length / 2;
-    match find_repeating_digits_of_size(input, window_size) {
-        Some(_digits) => {
-            Some(input)
-        },
-        None => None,
-    }
+/// Adds up the sums of the ranges, failing
+/// instead of wrapping around.
+pub fn checked_total(
+    sums: impl IntoIterator<Item = u128>,
+) -> Result<u128, GiftShopError> {
+    sums.into_iter().try_fold(0u128, |total, sum| {
+        total
+            .checked_add(sum)
+            .ok_or(GiftShopError::Overflow)
+    })
+}
 
-    // nvm this was not needed, learn how to read Stefan ;)
-    // for window_size in 1..=max_window_size {
-    //     if let Some(_digits) = find_repeating_digits_of_size(&input, window_size) {
-    //         return Some(input)
-    //     }
-    // }
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use rstest::rstest;
 
-    // None
-}
+    use super::*;
 
-fn find_repeating_digits_of_size(input: &str, window_size: usize) -> Option<&str> {
-    let full_length = input.len();
-    let first_index = 0;
-    let final_index = full_length - 2 * window_size;
+    /// The same as [`process`], but checking
+    /// every id in every range.
+    fn brute_force(input: &str) -> miette::Result<String> {
+        let invalid_ids = list_product_ids(input)?
+            .into_iter()
+            .filter(|id| {
+                find_repeating_digits(&id.to_string())
+                    .is_some()
+            });
+        Ok(checked_sum(invalid_ids)?.to_string())
+    }
 
-    for left_start_index in first_index..=final_index {
-        let left_end_index = left_start_index + window_size - 1;
-        let right_start_index = left_end_index + 1;
-        let right_end_index = right_start_index + window_size - 1;
+    fn list_product_ids(
+        input: &str,
+    ) -> Result<Vec<u64>, GiftShopError> {
+        Ok(parse_ranges(input)?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
 
-        let lhs = &input[left_start_index..=left_end_index];
-        let rhs = &input[right_start_index..=right_end_index];
+    /// Adds up the ids, failing instead of
+    /// wrapping around.
+    fn checked_sum(
+        ids: impl IntoIterator<Item = u64>,
+    ) -> Result<u128, GiftShopError> {
+        checked_total(ids.into_iter().map(u128::from))
+    }
 
-        if lhs == rhs {return Some(lhs)}
+    fn find_repeating_digits(input: &str) -> Option<&str> {
+        let full_length = input.len();
+        if full_length % 2 == 1 {
+            return None;
+        }
+        let window_size = full_length / 2;
+        match find_repeating_digits_of_size(
+            input,
+            window_size,
+        ) {
+            Some(_digits) => Some(input),
+            None => None,
+        }
     }
 
-    None
-}
+    fn find_repeating_digits_of_size(
+        input: &str,
+        window_size: usize,
+    ) -> Option<&str> {
+        let full_length = input.len();
+        let first_index = 0;
+        let final_index = full_length - 2 * window_size;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        for left_start_index in first_index..=final_index {
+            let left_end_index =
+                left_start_index + window_size - 1;
+            let right_start_index = left_end_index + 1;
+            let right_end_index =
+                right_start_index + window_size - 1;
+
+            let lhs =
+                &input[left_start_index..=left_end_index];
+            let rhs =
+                &input[right_start_index..=right_end_index];
+
+            if lhs == rhs {
+                return Some(lhs);
+            }
+        }
+
+        None
+    }
 
     #[test]
     fn test_process() -> miette::Result<()> {
-        let input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
+        let input = "11-22,95-115,998-1012,\
+            1188511880-1188511890,222220-222224,\
+            1698522-1698528,446443-446449,\
+            38593856-38593862,565653-565659,\
+            824824821-824824827,\
+            2121212118-2121212124";
         assert_eq!("1227775554", process(input)?);
+        assert_eq!("1227775554", brute_force(input)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_list_product_ids() -> miette::Result<()> {
+        assert_eq!(
+            list_product_ids("8-10,\n95-95\n")?,
+            [8, 9, 10, 95]
+        );
+        let error =
+            list_product_ids("11-22,95115").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected `-` at line 1, column 12"
+        );
         Ok(())
     }
 
+    #[rstest]
+    #[case::empty("")]
+    #[case::truncated("11-22,95-")]
+    #[case::garbage("11-22,ninety-five")]
+    #[case::too_large("1-99999999999999999999")]
+    fn test_malformed(#[case] input: &str) {
+        for result in
+            [process(input), crate::part2::process(input)]
+        {
+            assert!(matches!(
+                result.unwrap_err().downcast(),
+                Ok(GiftShopError::Parse(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_reversed() {
+        for result in [
+            process("22-11"),
+            crate::part2::process("22-11"),
+        ] {
+            assert!(matches!(
+                result.unwrap_err().downcast(),
+                Ok(GiftShopError::Reversed {
+                    start: 22,
+                    end: 11
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_checked_sum() {
+        assert_eq!(checked_sum([1, 2, 3]).ok(), Some(6));
+        assert_eq!(
+            checked_sum([u64::MAX, 1]).ok(),
+            Some(1 << 64)
+        );
+        assert!(matches!(
+            checked_total([u128::MAX, 1]),
+            Err(GiftShopError::Overflow)
+        ));
+    }
+
     #[test]
     fn test_find_repeating_digits_of_size() {
-        assert_eq!(find_repeating_digits_of_size("11", 1), Some("1"));
-        assert_eq!(find_repeating_digits_of_size("12", 1), None);
-        assert_eq!(find_repeating_digits_of_size("1212", 1), None);
-        assert_eq!(find_repeating_digits_of_size("1212", 2), Some("12"));
-        assert_eq!(find_repeating_digits_of_size("12323", 2), Some("23"));
-        assert_eq!(find_repeating_digits_of_size("123423", 2), None);
+        assert_eq!(
+            find_repeating_digits_of_size("11", 1),
+            Some("1")
+        );
+        assert_eq!(
+            find_repeating_digits_of_size("12", 1),
+            None
+        );
+        assert_eq!(
+            find_repeating_digits_of_size("1212", 1),
+            None
+        );
+        assert_eq!(
+            find_repeating_digits_of_size("1212", 2),
+            Some("12")
+        );
+        assert_eq!(
+            find_repeating_digits_of_size("12323", 2),
+            Some("23")
+        );
+        assert_eq!(
+            find_repeating_digits_of_size("123423", 2),
+            None
+        );
+    }
+
+    #[test]
+    fn test_wide_range() -> miette::Result<()> {
+        // every doubled id of up to 18 digits
+        assert_eq!(
+            process("1-999999999999999999")?,
+            "495495495540950040450040950"
+        );
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_brute_force(
+            start in 0..10u64.pow(12),
+            width in 0..2000u64,
+            digits in 1..=12u32,
+        ) {
+            // start at any number of digits, so short
+            // ids are as likely as long ones
+            let start = start % 10u64.pow(digits);
+            let input =
+                format!("{start}-{}", start + width);
+            prop_assert_eq!(
+                process(&input).unwrap(),
+                brute_force(&input).unwrap()
+            );
+        }
     }
 }
@@ -1,58 +1,80 @@
-use itertools::Itertools;
+use std::ops::RangeInclusive;
+
+use crate::{
+    invalid_ids::sum_repeated,
+    part1::{checked_total, parse_ranges},
+};
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String> {
-    Ok(list_product_ids(input)
-        .iter()
-        .filter(|id| has_repeating_digits(id))
-        .map(|id| id.parse::<u64>().unwrap())
-        .sum::<u64>()
-        .to_string())
-}
-
-fn list_product_ids(input: &str) -> Vec<String> {
-    input
-        .split(',')
-        .filter_map(|part| {
-            let (from_str, to_str) =
-                part.trim().split_once('-')?;
-            let from =
-                from_str.trim().parse::<u64>().ok()?;
-            let to = to_str.trim().parse::<u64>().ok()?;
-            Some(from..=to)
-        })
-        .flat_map(|range| range.map(|n| n.to_string()))
-        .collect()
-}
-
-fn get_divisors(n: usize) -> Vec<usize> {
-    (1..=n).filter(|d| n % d == 0).collect()
+    let ranges = parse_ranges(input)?;
+    solve(&ranges)
 }
 
-fn has_repeating_digits(input: &str) -> bool {
-    get_divisors(input.len()).into_iter().any(
-        |split_size| {
-            has_repeating_digits_of_size(input, split_size)
-        },
+pub fn solve(
+    ranges: &[RangeInclusive<u64>],
+) -> miette::Result<String> {
+    Ok(
+        checked_total(ranges.iter().map(sum_repeated))?
+            .to_string(),
     )
 }
 
-fn has_repeating_digits_of_size(
-    input: &str,
-    split_size: usize,
-) -> bool {
-    split_size < input.len()
-        && input.as_bytes().chunks(split_size).all_equal()
-}
-
 #[cfg(test)]
 mod tests {
+    use itertools::Itertools;
+    use proptest::prelude::*;
+
     use super::*;
 
+    /// The same as [`process`], but checking
+    /// every id in every range.
+    fn brute_force(input: &str) -> miette::Result<String> {
+        let invalid_ids = parse_ranges(input)?
+            .into_iter()
+            .flatten()
+            .filter(|id| {
+                has_repeating_digits(&id.to_string())
+            })
+            .map(u128::from);
+        Ok(checked_total(invalid_ids)?.to_string())
+    }
+
+    fn get_divisors(n: usize) -> Vec<usize> {
+        (1..=n).filter(|d| n.is_multiple_of(*d)).collect()
+    }
+
+    fn has_repeating_digits(input: &str) -> bool {
+        get_divisors(input.len()).into_iter().any(
+            |split_size| {
+                has_repeating_digits_of_size(
+                    input, split_size,
+                )
+            },
+        )
+    }
+
+    fn has_repeating_digits_of_size(
+        input: &str,
+        split_size: usize,
+    ) -> bool {
+        split_size < input.len()
+            && input
+                .as_bytes()
+                .chunks(split_size)
+                .all_equal()
+    }
+
     #[test]
     fn test_process() -> miette::Result<()> {
-        let input = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
+        let input = "11-22,95-115,998-1012,\
+            1188511880-1188511890,222220-222224,\
+            1698522-1698528,446443-446449,\
+            38593856-38593862,565653-565659,\
+            824824821-824824827,\
+            2121212118-2121212124";
         assert_eq!("4174379265", process(input)?);
+        assert_eq!("4174379265", brute_force(input)?);
         Ok(())
     }
 
@@ -77,4 +99,21 @@ mod tests {
         assert!(!has_repeating_digits_of_size("1212", 1));
         assert!(has_repeating_digits_of_size("1212", 2));
     }
+
+    proptest! {
+        #[test]
+        fn prop_matches_brute_force(
+            start in 0..10u64.pow(12),
+            width in 0..2000u64,
+            digits in 1..=12u32,
+        ) {
+            let start = start % 10u64.pow(digits);
+            let input =
+                format!("{start}-{}", start + width);
+            prop_assert_eq!(
+                process(&input).unwrap(),
+                brute_force(&input).unwrap()
+            );
+        }
+    }
 }
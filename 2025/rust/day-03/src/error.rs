@@ -0,0 +1,27 @@
+use aoc_core::parse::ParseError;
+
+/// Why the banks of batteries can't be switched
+/// on.
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum LobbyError {
+    /// A line that isn't a bank of digits.
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Parse(#[from] ParseError),
+    #[error(
+        "bank {line} has {found} batteries, but \
+         {needed} should be turned on"
+    )]
+    #[diagnostic(
+        code(day_03::too_few_batteries),
+        help(
+            "every bank needs at least as many batteries \
+             as are turned on"
+        )
+    )]
+    TooFewBatteries {
+        line: usize,
+        found: usize,
+        needed: usize,
+    },
+}
@@ -1,2 +1,29 @@
+use aoc_core::Solution;
+
+pub use crate::error::LobbyError;
+
+mod error;
 pub mod part1;
 pub mod part2;
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2025;
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Lobby";
+
+    type Parsed<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> miette::Result<Vec<&str>> {
+        Ok(part1::parse(input)?)
+    }
+
+    fn part1(banks: &Vec<&str>) -> miette::Result<String> {
+        part1::solve(banks)
+    }
+
+    fn part2(banks: &Vec<&str>) -> miette::Result<String> {
+        part2::solve(banks)
+    }
+}
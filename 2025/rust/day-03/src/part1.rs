@@ -1,28 +1,56 @@
+use aoc_core::parse::{Span, finish, lines};
+use nom::{Parser, character::complete::digit1};
+
+use crate::LobbyError;
+
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String> {
+    let banks = parse(input)?;
+    solve(&banks)
+}
+
+pub fn solve(banks: &[&str]) -> miette::Result<String> {
+    check_batteries(banks, 2)?;
     Ok(
-        input
-        .lines()
+        banks
+        .iter()
         .map(|bank| find_maximum_bank_joltage(bank))
         .sum::<u32>()
         .to_string()
     )
 }
 
+/// The banks of batteries, one line of digits each.
+pub fn parse(input: &str) -> Result<Vec<&str>, LobbyError> {
+    Ok(finish(input, lines(digit1.map(|bank: Span| *bank.fragment())))?)
+}
+
+/// Checks that every bank has at least `batteries` batteries to turn on.
+pub fn check_batteries(banks: &[&str], batteries: usize) -> Result<(), LobbyError> {
+    match banks.iter().enumerate().find(|(_, bank)| bank.len() < batteries) {
+        Some((line, bank)) => Err(LobbyError::TooFewBatteries {
+            line: line + 1,
+            found: bank.len(),
+            needed: batteries,
+        }),
+        None => Ok(()),
+    }
+}
+
 fn find_maximum_bank_joltage(input: &str) -> u32 {
     let input_length = input.len();
     let (first_digit_index, first_digit) = find_largest_digit(&input[..input_length-1]);
     let (_second_digit_index, second_digit) = find_largest_digit(&input[first_digit_index+1..input_length]);
-    let first_digit_u32 = first_digit.to_digit(10).unwrap();
-    let second_digit_u32 = second_digit.to_digit(10).unwrap();
-    let number = first_digit_u32 * 10 + second_digit_u32;
-    number
+    first_digit * 10 + second_digit
 }
 
-pub fn find_largest_digit(input: &str) -> (usize, char) {
+/// The first of the largest digits and its index, skipping anything
+/// that isn't a digit.
+pub fn find_largest_digit(input: &str) -> (usize, u32) {
     input
         .char_indices()
-        .fold((0, '0'), |(highest_index, highest_value), (current_index, current_value)| {
+        .filter_map(|(index, ch)| Some((index, ch.to_digit(10)?)))
+        .fold((0, 0), |(highest_index, highest_value), (current_index, current_value)| {
             match current_value.cmp(&highest_value) {
                 std::cmp::Ordering::Less => (highest_index, highest_value),
                 std::cmp::Ordering::Equal => (highest_index, highest_value),
@@ -33,6 +61,8 @@ pub fn find_largest_digit(input: &str) -> (usize, char) {
 
 #[cfg(test)]
 mod tests {
+    use rstest::rstest;
+
     use super::*;
 
     #[test]
@@ -54,8 +84,40 @@ mod tests {
 
     #[test]
     fn test_find_largest_digit() {
-        assert_eq!(find_largest_digit("01234"), (4, '4'));
-        assert_eq!(find_largest_digit("50123"), (0, '5'));
-        assert_eq!(find_largest_digit("12321"), (2, '3'));
+        assert_eq!(find_largest_digit("01234"), (4, 4));
+        assert_eq!(find_largest_digit("50123"), (0, 5));
+        assert_eq!(find_largest_digit("12321"), (2, 3));
+    }
+
+    #[test]
+    fn test_too_few_batteries() {
+        assert!(matches!(
+            parse("987654321111111\n811111111111119\n2")
+                .and_then(|banks| check_batteries(&banks, 2)),
+            Err(LobbyError::TooFewBatteries { line: 3, found: 1, needed: 2 })
+        ));
+        let truncated = "987654321111111\n8";
+        assert!(matches!(
+            process(truncated).unwrap_err().downcast(),
+            Ok(LobbyError::TooFewBatteries { line: 2, found: 1, needed: 2 })
+        ));
+        assert!(matches!(
+            crate::part2::process(truncated).unwrap_err().downcast(),
+            Ok(LobbyError::TooFewBatteries { line: 2, found: 1, needed: 12 })
+        ));
+    }
+
+    #[rstest]
+    #[case::empty("")]
+    #[case::blank_line("987654321111111\n\n811111111111119")]
+    #[case::garbage("98765432111111a")]
+    #[case::multibyte("9876543211111\u{e9}")]
+    fn test_malformed(#[case] input: &str) {
+        for result in [process(input), crate::part2::process(input)] {
+            assert!(matches!(
+                result.unwrap_err().downcast(),
+                Ok(LobbyError::Parse(_))
+            ));
+        }
     }
 }
@@ -1,11 +1,19 @@
-use crate::part1::find_largest_digit;
+use crate::part1::{check_batteries, find_largest_digit, parse};
+
+const BATTERIES: u32 = 12;
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String> {
+    let banks = parse(input)?;
+    solve(&banks)
+}
+
+pub fn solve(banks: &[&str]) -> miette::Result<String> {
+    check_batteries(banks, BATTERIES as usize)?;
     Ok(
-        input
-        .lines()
-        .map(|bank| find_joltage_for_n_batteries(bank, 12))
+        banks
+        .iter()
+        .map(|bank| find_joltage_for_n_batteries(bank, BATTERIES))
         .sum::<u64>()
         .to_string()
     )
@@ -18,7 +26,7 @@ fn find_joltage_for_n_batteries(input: &str, number_of_batteries: u32) -> u64 {
         let max_allowed_index = input.len() - (remaining_batteries as usize);
         let search_string = &input[start_index..max_allowed_index];
         let (index, digit) = find_largest_digit(search_string);
-        let value = (digit.to_digit(10).unwrap() as u64) * 10_u64.pow(remaining_batteries);
+        let value = u64::from(digit) * 10_u64.pow(remaining_batteries);
 
         (joltage + value, start_index + index + 1)
     }).0
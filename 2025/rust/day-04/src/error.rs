@@ -0,0 +1,12 @@
+use aoc_grid::GridError;
+
+/// Why the diagram of the paper rolls can't be
+/// read.
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum PrintingError {
+    /// A diagram that isn't a rectangle of `.`
+    /// and `@`.
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Grid(#[from] GridError),
+}
@@ -1,2 +1,30 @@
+use aoc_core::Solution;
+use aoc_grid::Grid;
+
+pub use crate::error::PrintingError;
+
+mod error;
 pub mod part1;
 pub mod part2;
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2025;
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Printing Department";
+
+    type Parsed<'a> = Grid<bool>;
+
+    fn parse(input: &str) -> miette::Result<Grid<bool>> {
+        Ok(part1::make_grid(input)?)
+    }
+
+    fn part1(grid: &Grid<bool>) -> miette::Result<String> {
+        part1::solve(grid)
+    }
+
+    fn part2(grid: &Grid<bool>) -> miette::Result<String> {
+        part2::solve(grid)
+    }
+}
@@ -0,0 +1,97 @@
+//! The merged fresh ranges against scanning every
+//! range, on generated inventories far larger
+//! than the puzzle's. Both count their ids, so
+//! divan shows the time per query side by side.
+//! The merged ranges are the `IntervalSet` part 1
+//! asks, so this times the real lookups.
+
+use std::ops::RangeInclusive;
+
+use aoc_collections::IntervalSet;
+use divan::counter::ItemsCount;
+
+fn main() {
+    divan::main();
+}
+
+const RANGES: [usize; 3] = [1_000, 10_000, 50_000];
+/// The ids asked of the merged ranges.
+const IDS: usize = 2_000_000;
+/// The ids asked of the scan, which would take
+/// minutes for all of them.
+const SCANNED_IDS: usize = 10_000;
+/// Ids go up to this and ranges are up to a
+/// hundred-thousandth of it long, so ranges
+/// overlap but most of them stay apart after
+/// merging.
+const MAX_ID: u64 = 1_000_000_000_000;
+
+/// Deterministic pseudo random numbers below
+/// `MAX_ID`.
+fn numbers(seed: u64) -> impl Iterator<Item = u64> {
+    let mut state = seed;
+    std::iter::repeat_with(move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state % MAX_ID
+    })
+}
+
+fn fresh_ranges(count: usize) -> Vec<RangeInclusive<u64>> {
+    numbers(0x2545_f491_4f6c_dd1d)
+        .take(count)
+        .map(|start| {
+            start..=start + start % (MAX_ID / 100_000)
+        })
+        .collect()
+}
+
+/// Ids streamed straight from the generator,
+/// never collected.
+fn ids() -> impl Iterator<Item = u64> {
+    numbers(0x9e37_79b9_7f4a_7c15)
+}
+
+#[divan::bench(args = RANGES)]
+fn build(bencher: divan::Bencher, ranges: usize) {
+    let ranges = fresh_ranges(ranges);
+    bencher.bench(|| {
+        divan::black_box(&ranges)
+            .iter()
+            .cloned()
+            .collect::<IntervalSet<u64>>()
+    });
+}
+
+#[divan::bench(args = RANGES, sample_count = 10)]
+fn merged(bencher: divan::Bencher, ranges: usize) {
+    let fresh = fresh_ranges(ranges)
+        .into_iter()
+        .collect::<IntervalSet<u64>>();
+    bencher.counter(ItemsCount::new(IDS)).bench(|| {
+        ids()
+            .take(IDS)
+            .filter(|&id| {
+                divan::black_box(&fresh).contains(id)
+            })
+            .count()
+    });
+}
+
+#[divan::bench(args = RANGES, sample_count = 10)]
+fn linear_scan(bencher: divan::Bencher, ranges: usize) {
+    let ranges = fresh_ranges(ranges);
+    bencher.counter(ItemsCount::new(SCANNED_IDS)).bench(
+        || {
+            ids()
+                .take(SCANNED_IDS)
+                .filter(|id| {
+                    divan::black_box(&ranges)
+                        .iter()
+                        .any(|range| range.contains(id))
+                })
+                .count()
+        },
+    );
+}
@@ -5,8 +5,10 @@ use miette::Context;
 fn main() -> miette::Result<()> {
     tracing_subscriber::fmt::init();
 
-    let file = include_str!("../../input2.txt");
-    let result = process(file).context("process part 2")?;
+    let file =
+        aoc_core::input::load(env!("CARGO_MANIFEST_DIR"))?;
+    let result =
+        process(&file).context("process part 2")?;
     println!("{}", result);
     Ok(())
 }
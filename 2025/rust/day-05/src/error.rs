@@ -0,0 +1,23 @@
+use aoc_core::parse::ParseError;
+
+/// Why the cafeteria's inventory can't be read.
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum CafeteriaError {
+    /// An inventory that isn't ranges like `3-5`,
+    /// a blank line and then ids.
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Parse(#[from] ParseError),
+    #[error(
+        "the fresh range {start}-{end} on line {line} \
+         ends before it starts"
+    )]
+    #[diagnostic(
+        code(day_05::reversed),
+        help(
+            "ranges go from the first fresh id to the \
+             last, e.g. `3-5`"
+        )
+    )]
+    Reversed { line: usize, start: u64, end: u64 },
+}
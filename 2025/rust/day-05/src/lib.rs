@@ -1,2 +1,30 @@
+use aoc_core::Solution;
+
+pub use crate::error::CafeteriaError;
+use crate::part1::Inventory;
+
+mod error;
 pub mod part1;
 pub mod part2;
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2025;
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Cafeteria";
+
+    type Parsed<'a> = Inventory;
+
+    fn parse(input: &str) -> miette::Result<Inventory> {
+        Ok(part1::parse(input)?)
+    }
+
+    fn part1(inventory: &Inventory) -> miette::Result<String> {
+        part1::solve(inventory)
+    }
+
+    fn part2(inventory: &Inventory) -> miette::Result<String> {
+        part2::solve(inventory)
+    }
+}
@@ -1,43 +1,75 @@
+use std::ops::RangeInclusive;
+
+use aoc_collections::IntervalSet;
+use aoc_core::parse::{
+    finish, lines, range, two_sections, unsigned,
+};
+
+use crate::CafeteriaError;
+
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String> {
-    let (fresh_ranges, available_ingredients) = input
-        .split_once("\n\n")
-        .unwrap();
-
-    let fresh_ranges = fresh_ranges
-        .lines()
-        .map(|range| range.split_once('-').unwrap())
-        .map(|(start_str, end_str)| {
-            let start = start_str.parse::<u64>().unwrap();
-            let end = end_str.parse::<u64>().unwrap();
-            start..=end
-        })
-        .collect::<Vec<_>>();
-
-    let available_ingredients = available_ingredients
-        .lines()
-        .map(|ingredient_id| ingredient_id.parse::<u64>().unwrap())
-        .collect::<Vec<_>>();
-
-    let fresh_ingredient_count = available_ingredients
+    let inventory = parse(input)?;
+    solve(&inventory)
+}
+
+pub fn solve(inventory: &Inventory) -> miette::Result<String> {
+    let fresh = inventory.fresh_ids();
+
+    let fresh_ingredient_count = inventory
+        .available_ingredients
         .iter()
-        .filter(|ingredient_id| {
-            fresh_ranges
-                .iter()
-                .any(|fresh_range| fresh_range.contains(&ingredient_id))}
-        )
+        .filter(|&&ingredient_id| fresh.contains(ingredient_id))
         .count();
-        
 
-    // println!("{fresh_ranges:?}");
+    Ok(fresh_ingredient_count.to_string())
+}
+
+/// The ranges of fresh ingredient ids, then the
+/// ids of the available ingredients.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Inventory {
+    pub fresh_ranges: Vec<RangeInclusive<u64>>,
+    pub available_ingredients: Vec<u64>,
+}
 
-    // todo!();
+impl Inventory {
+    /// Every fresh id, with the overlapping ranges
+    /// merged.
+    pub fn fresh_ids(&self) -> IntervalSet<u64> {
+        self.fresh_ranges.iter().cloned().collect()
+    }
+}
 
-    Ok(fresh_ingredient_count.to_string())
+pub fn parse(
+    input: &str,
+) -> Result<Inventory, CafeteriaError> {
+    let (fresh_ranges, available_ingredients) = finish(
+        input,
+        two_sections(lines(range), lines(unsigned)),
+    )?;
+    if let Some((line, range)) = fresh_ranges
+        .iter()
+        .enumerate()
+        .find(|(_, range)| range.is_empty())
+    {
+        return Err(CafeteriaError::Reversed {
+            line: line + 1,
+            start: *range.start(),
+            end: *range.end(),
+        });
+    }
+    Ok(Inventory {
+        fresh_ranges,
+        available_ingredients,
+    })
 }
 
 #[cfg(test)]
 mod tests {
+    use proptest::prelude::*;
+    use rstest::rstest;
+
     use super::*;
 
     #[test]
@@ -56,4 +88,98 @@ mod tests {
         assert_eq!("3", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_parse() -> miette::Result<()> {
+        assert_eq!(
+            parse("3-5\n10-14\n\n1\n5\n")?,
+            Inventory {
+                fresh_ranges: vec![3..=5, 10..=14],
+                available_ingredients: vec![1, 5],
+            }
+        );
+        let error = parse("3-5\n10-x\n\n1").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected an unsigned integer at line 2, column 4"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_reversed() {
+        assert!(matches!(
+            parse("3-5\n14-10\n\n1"),
+            Err(CafeteriaError::Reversed {
+                line: 2,
+                start: 14,
+                end: 10
+            })
+        ));
+        let input = "5-3\n\n4";
+        for result in [process(input), crate::part2::process(input)] {
+            assert!(matches!(
+                result.unwrap_err().downcast(),
+                Ok(CafeteriaError::Reversed { line: 1, start: 5, end: 3 })
+            ));
+        }
+    }
+
+    #[rstest]
+    #[case::empty("")]
+    #[case::no_ingredients("3-5\n10-14")]
+    #[case::truncated("3-5\n10-")]
+    #[case::garbage("3-5\n\nfive")]
+    #[case::too_large("3-99999999999999999999\n\n4")]
+    fn test_malformed(#[case] input: &str) {
+        for result in [process(input), crate::part2::process(input)] {
+            assert!(matches!(
+                result.unwrap_err().downcast(),
+                Ok(CafeteriaError::Parse(_))
+            ));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_process(
+            ranges in prop::collection::vec(
+                (0..200u64, 0..20u64),
+                1..10,
+            ),
+            ids in prop::collection::vec(0..250u64, 1..20),
+        ) {
+            // which of the ids up to 250 are fresh
+            let mut fresh = [false; 250];
+            for &(start, len) in &ranges {
+                fresh[start as usize..=(start + len) as usize]
+                    .fill(true);
+            }
+            let ranges = ranges
+                .iter()
+                .map(|(start, len)| {
+                    format!("{start}-{}", start + len)
+                })
+                .collect::<Vec<_>>();
+            let ids = ids
+                .iter()
+                .map(u64::to_string)
+                .collect::<Vec<_>>();
+            let input =
+                format!("{}\n\n{}", ranges.join("\n"), ids.join("\n"));
+
+            let fresh_ingredients = ids
+                .iter()
+                .filter(|id| fresh[id.parse::<usize>().unwrap()])
+                .count();
+            prop_assert_eq!(
+                process(&input).unwrap(),
+                fresh_ingredients.to_string()
+            );
+            prop_assert_eq!(
+                crate::part2::process(&input).unwrap(),
+                fresh.iter().filter(|&&fresh| fresh).count().to_string()
+            );
+        }
+    }
 }
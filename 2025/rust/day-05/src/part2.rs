@@ -1,30 +1,15 @@
-use std::cmp::Ordering;
+use crate::part1::{Inventory, parse};
 
-use itertools::Itertools;
-
-// #[tracing::instrument]
+#[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String> {
-    Ok(input
-        .split_once("\n\n")
-        .ok_or_else(|| miette::miette!("invalid input"))?
-        .0
-        .lines()
-        .map(|range| range.split_once('-').unwrap())
-        .map(|(start, end)| (start.parse::<u64>().unwrap(), end.parse::<u64>().unwrap()))
-        .sorted_by(|(a_start, _a_end), (b_start, _b_end)| Ord::cmp(&a_start, &b_start))
-        .fold((0, None::<u64>), |(fresh_id_count, current_max_id), (start, end)| {
-            let Some(highest_seen_id) = current_max_id else {
-                return (end-start+1,Some(end))
-            };
-            match ( start.cmp(&highest_seen_id), end.cmp(&highest_seen_id) ) {
-                (Ordering::Greater, _) => (fresh_id_count + (end-       start       +1), Some(end)),
-                (_, Ordering::Greater) => (fresh_id_count + (end-(highest_seen_id+1)+1), Some(end)),
-                (_, _                ) => (fresh_id_count, Some(highest_seen_id))
-            }
-        })
-        .0
-        .to_string()
-    )
+    let inventory = parse(input)?;
+    solve(&inventory)
+}
+
+pub fn solve(inventory: &Inventory) -> miette::Result<String> {
+    // counted in a u128, `0-18446744073709551615`
+    // holds one id too many for a u64
+    Ok(inventory.fresh_ids().covered_len().to_string())
 }
 
 #[cfg(test)]
@@ -47,4 +32,10 @@ mod tests {
         assert_eq!("14", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_every_id() -> miette::Result<()> {
+        assert_eq!("18446744073709551616", process("0-18446744073709551615\n\n1")?);
+        Ok(())
+    }
 }
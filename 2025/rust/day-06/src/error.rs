@@ -0,0 +1,65 @@
+use std::num::ParseIntError;
+
+/// Why the cephalopods' math homework can't be
+/// worked out.
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum CompactorError {
+    #[error("the worksheet has no line of operators")]
+    #[diagnostic(
+        code(day_06::missing_operators),
+        help(
+            "the last line should hold a `+` or `*` \
+             under every problem"
+        )
+    )]
+    MissingOperators,
+    #[error(
+        "problem {problem} has no number on line {line}"
+    )]
+    #[diagnostic(
+        code(day_06::missing_number),
+        help(
+            "every line above the operators needs a \
+             number for every problem"
+        )
+    )]
+    MissingNumber { line: usize, problem: usize },
+    #[error(
+        "line {line} has more numbers than the {problems} \
+         operators"
+    )]
+    #[diagnostic(
+        code(day_06::extra_number),
+        help(
+            "every number needs an operator under its \
+             problem"
+        )
+    )]
+    ExtraNumber { line: usize, problems: usize },
+    #[error(
+        "problem {problem} has `{number}` for a number"
+    )]
+    #[diagnostic(code(day_06::invalid_number))]
+    InvalidNumber {
+        number: String,
+        problem: usize,
+        #[source]
+        source: ParseIntError,
+    },
+    #[error("problem {problem} has `{op}` for an operator")]
+    #[diagnostic(
+        code(day_06::unknown_operator),
+        help(
+            "problems are either added up with `+` or multiplied with `*`"
+        )
+    )]
+    UnknownOperator { op: String, problem: usize },
+    #[error("the answers don't fit in an integer")]
+    #[diagnostic(
+        code(day_06::overflow),
+        help(
+            "the numbers are far larger than any puzzle input"
+        )
+    )]
+    Overflow,
+}
@@ -1,2 +1,34 @@
+use aoc_core::Solution;
+
+pub use crate::error::CompactorError;
+use crate::part1::Worksheet;
+
+mod error;
 pub mod part1;
 pub mod part2;
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2025;
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Trash Compactor";
+
+    type Parsed<'a> = Worksheet<'a>;
+
+    fn parse(input: &str) -> miette::Result<Worksheet<'_>> {
+        Ok(part1::parse(input)?)
+    }
+
+    fn part1(
+        worksheet: &Worksheet<'_>,
+    ) -> miette::Result<String> {
+        part1::solve(worksheet)
+    }
+
+    fn part2(
+        worksheet: &Worksheet<'_>,
+    ) -> miette::Result<String> {
+        part2::solve(worksheet)
+    }
+}
@@ -1,34 +1,98 @@
+use crate::CompactorError;
+
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String> {
-    let mut lines = input
-        .lines()
-        .into_iter()
-        .map(|line| {
-            line.split(' ')
-                .filter(|item| !item.is_empty())
-        });
-    let ops = lines.next_back().unwrap();
-    let mut nums = lines.collect::<Vec<_>>();
-
-    let total = ops.fold(0, |total_acc, op| {
-        let col_total = total_acc + nums
-            .iter_mut()
-            .fold(0, |col_acc, line| {
-                let num = line.next().unwrap().parse::<u128>().unwrap();
-                match op {
-                    "+" => col_acc + num,
-                    "*" => if col_acc == 0 {num} else {col_acc * num},
-                    _ => col_acc,
-                }
+    let worksheet = parse(input)?;
+    solve(&worksheet)
+}
+
+/// The lines of numbers and the line of operators
+/// below them. The parts read the numbers in
+/// different directions, so they stay text.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Worksheet<'a> {
+    pub rows: Vec<&'a str>,
+    pub operators: &'a str,
+}
+
+pub fn parse(
+    input: &str,
+) -> Result<Worksheet<'_>, CompactorError> {
+    let mut rows = input.lines().collect::<Vec<_>>();
+    let operators = rows
+        .pop()
+        .ok_or(CompactorError::MissingOperators)?;
+    // a number past the last operator belongs to no
+    // problem, so a ragged row is a malformed sheet
+    let problems = split(operators).count();
+    for (line, row) in rows.iter().enumerate() {
+        if split(row).count() > problems {
+            return Err(CompactorError::ExtraNumber {
+                line: line + 1,
+                problems,
             });
-        col_total
-    });
+        }
+    }
+    Ok(Worksheet { rows, operators })
+}
+
+/// The whitespace-separated items of a line.
+fn split(line: &str) -> impl Iterator<Item = &str> {
+    line.split(' ').filter(|item| !item.is_empty())
+}
+
+pub fn solve(
+    worksheet: &Worksheet,
+) -> miette::Result<String> {
+    let ops = split(worksheet.operators);
+    let mut nums = worksheet
+        .rows
+        .iter()
+        .map(|line| split(line))
+        .collect::<Vec<_>>();
+
+    let total = ops.enumerate().try_fold(0, |total_acc: u128, (problem, op)| {
+        let problem = problem + 1;
+        type Apply = fn(u128, u128) -> Option<u128>;
+        // each problem starts from its operator's
+        // identity, so a zero in a product stays zero
+        let (start, apply): (u128, Apply) =
+            match op {
+                "+" => (0, u128::checked_add),
+                "*" => (1, u128::checked_mul),
+                _ => {
+                    return Err(CompactorError::UnknownOperator {
+                        op: op.to_string(),
+                        problem,
+                    });
+                }
+            };
+        let answer = nums
+            .iter_mut()
+            .enumerate()
+            .try_fold(start, |col_acc, (line, numbers)| {
+                let number = numbers.next().ok_or(
+                    CompactorError::MissingNumber { line: line + 1, problem },
+                )?;
+                let num = number.parse::<u128>().map_err(|source| {
+                    CompactorError::InvalidNumber {
+                        number: number.to_string(),
+                        problem,
+                        source,
+                    }
+                })?;
+                apply(col_acc, num).ok_or(CompactorError::Overflow)
+            })?;
+        total_acc.checked_add(answer).ok_or(CompactorError::Overflow)
+    })?;
 
     Ok(total.to_string())
 }
 
 #[cfg(test)]
 mod tests {
+    use rstest::rstest;
+
     use super::*;
 
     #[test]
@@ -40,4 +104,100 @@ mod tests {
         assert_eq!("4277556", process(input)?);
         Ok(())
     }
+
+    /// Both parts' errors on `input`.
+    fn errors(input: &str) -> [miette::Report; 2] {
+        [process(input), crate::part2::process(input)]
+            .map(Result::unwrap_err)
+    }
+
+    #[test]
+    fn test_missing_operators() {
+        for error in errors("") {
+            assert!(matches!(
+                error.downcast(),
+                Ok(CompactorError::MissingOperators)
+            ));
+        }
+    }
+
+    #[rstest]
+    #[case::unknown_operator("1 2\n3 4\n* -", 2)]
+    #[case::only_operators("-", 1)]
+    fn test_unknown_operator(
+        #[case] input: &str,
+        #[case] expected: usize,
+    ) {
+        for error in errors(input) {
+            assert!(matches!(
+                error.downcast(),
+                Ok(CompactorError::UnknownOperator { op, problem })
+                    if op == "-" && problem == expected
+            ));
+        }
+    }
+
+    #[test]
+    fn test_invalid_number() {
+        for error in errors("1 2\n3 x\n* +") {
+            assert!(matches!(
+                error.downcast(),
+                Ok(CompactorError::InvalidNumber {
+                    problem: 2,
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_overflow() {
+        let input =
+            "99999999999999999999\n99999999999999999999\n*";
+        for error in errors(input) {
+            assert!(matches!(
+                error.downcast(),
+                Ok(CompactorError::Overflow)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_missing_number() {
+        assert!(matches!(
+            process("1 2\n3\n* +").unwrap_err().downcast(),
+            Ok(CompactorError::MissingNumber {
+                line: 2,
+                problem: 2
+            })
+        ));
+    }
+
+    #[rstest]
+    #[case::extra_number("1 2 3\n4 5\n* +", 1)]
+    #[case::extra_line("1 2\n3 4 5\n* +", 2)]
+    fn test_extra_number(
+        #[case] input: &str,
+        #[case] expected: usize,
+    ) {
+        for error in errors(input) {
+            assert!(matches!(
+                error.downcast(),
+                Ok(CompactorError::ExtraNumber { line, problems: 2 })
+                    if line == expected
+            ));
+        }
+    }
+
+    #[rstest]
+    #[case::zero_product("0\n5\n*", "0")]
+    #[case::zero_sum("0\n0\n+", "0")]
+    #[case::single_product("7\n*", "7")]
+    fn test_identity(
+        #[case] input: &str,
+        #[case] expected: &str,
+    ) -> miette::Result<()> {
+        assert_eq!(expected, process(input)?);
+        Ok(())
+    }
 }
@@ -1,43 +1,94 @@
+use crate::{
+    CompactorError,
+    part1::{Worksheet, parse},
+};
+
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String> {
-    let mut rows: Vec<Vec<char>> = input
-        .lines()
+    let worksheet = parse(input)?;
+    solve(&worksheet)
+}
+
+pub fn solve(
+    worksheet: &Worksheet,
+) -> miette::Result<String> {
+    let rows: Vec<Vec<char>> = worksheet
+        .rows
+        .iter()
         .map(|line| line.chars().collect())
         .collect();
-    let op_row = rows.pop().unwrap();
-    let height = rows.len();
+    let op_row: Vec<char> =
+        worksheet.operators.chars().collect();
+    // trailing spaces may have been trimmed from any
+    // of the lines, so read missing cells as blank
+    let width = rows
+        .iter()
+        .chain([&op_row])
+        .map(Vec::len)
+        .max()
+        .unwrap_or_default();
+    let cell = |row: &[char], x: usize| {
+        row.get(x).copied().unwrap_or(' ')
+    };
 
-    let mut grand_total: u64 = 0;
-    let mut current_total: u64 = 0;
-    let mut current_op: fn(u64, u64) -> u64 = u64::saturating_add;
+    let mut grand_total: u128 = 0;
+    let mut current_total: u128 = 0;
+    let mut current_op: fn(u128, u128) -> Option<u128> =
+        u128::checked_add;
+    let mut problem = 0;
 
-    for (x, &op) in op_row.iter().enumerate() {
-        match op {
+    for x in 0..width {
+        match cell(&op_row, x) {
             '+' => {
-                grand_total += current_total;
+                grand_total = grand_total
+                    .checked_add(current_total)
+                    .ok_or(CompactorError::Overflow)?;
                 current_total = 0;
-                current_op = u64::saturating_add;
+                current_op = u128::checked_add;
+                problem += 1;
             }
             '*' => {
-                grand_total += current_total;
+                grand_total = grand_total
+                    .checked_add(current_total)
+                    .ok_or(CompactorError::Overflow)?;
                 current_total = 1;
-                current_op = u64::saturating_mul;
+                current_op = u128::checked_mul;
+                problem += 1;
+            }
+            ' ' => {}
+            op => {
+                return Err(
+                    CompactorError::UnknownOperator {
+                        op: op.to_string(),
+                        problem: problem + 1,
+                    }
+                    .into(),
+                );
             }
-            _ => {}
         }
 
-        let column: String = rows
-            .iter()
-            .take(height)
-            .map(|row| row[x])
-            .collect();
-
-        if let Ok(number) = column.trim().parse::<u64>() {
-            current_total = current_op(current_total, number);
+        let column: String =
+            rows.iter().map(|row| cell(row, x)).collect();
+        let digits = column.trim();
+        if digits.is_empty() {
+            continue;
         }
+
+        let number =
+            digits.parse::<u128>().map_err(|source| {
+                CompactorError::InvalidNumber {
+                    number: digits.to_string(),
+                    problem,
+                    source,
+                }
+            })?;
+        current_total = current_op(current_total, number)
+            .ok_or(CompactorError::Overflow)?;
     }
 
-    grand_total += current_total;
+    grand_total = grand_total
+        .checked_add(current_total)
+        .ok_or(CompactorError::Overflow)?;
     Ok(grand_total.to_string())
 }
 
@@ -54,4 +105,14 @@ mod tests {
         assert_eq!("3263827", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_trimmed_lines() -> miette::Result<()> {
+        let input = "123 328  51 64
+ 45 64  387 23
+  6 98  215 314
+*   +   *   +";
+        assert_eq!("3263827", process(input)?);
+        Ok(())
+    }
 }
@@ -0,0 +1,36 @@
+use aoc_core::parse::ParseError;
+
+/// Why the tachyon beams can't be traced through
+/// the manifold.
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum ManifoldError {
+    /// A diagram that isn't a rectangle of `.`,
+    /// `^` and `S`.
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Parse(#[from] ParseError),
+    #[error("the diagram of the manifold is empty")]
+    #[diagnostic(
+        code(day_07::empty),
+        help(
+            "the first line should hold the `S` the beam enters at"
+        )
+    )]
+    Empty,
+    #[error(
+        "there is an `S` at line {line}, column {column}"
+    )]
+    #[diagnostic(
+        code(day_07::misplaced_start),
+        help("the beam can only enter at the first line")
+    )]
+    MisplacedStart { line: usize, column: usize },
+    #[error("there are too many timelines to count")]
+    #[diagnostic(
+        code(day_07::overflow),
+        help(
+            "the manifold is far larger than any puzzle input"
+        )
+    )]
+    Overflow,
+}
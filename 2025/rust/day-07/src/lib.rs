@@ -1,2 +1,33 @@
+use aoc_core::Solution;
+
+pub use crate::error::ManifoldError;
+
+mod error;
 pub mod part1;
 pub mod part2;
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2025;
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "Laboratories";
+
+    type Parsed<'a> = Vec<Vec<char>>;
+
+    fn parse(input: &str) -> miette::Result<Vec<Vec<char>>> {
+        Ok(part1::parse(input)?)
+    }
+
+    fn part1(
+        diagram: &Vec<Vec<char>>,
+    ) -> miette::Result<String> {
+        part1::solve(diagram)
+    }
+
+    fn part2(
+        diagram: &Vec<Vec<char>>,
+    ) -> miette::Result<String> {
+        part2::solve(diagram)
+    }
+}
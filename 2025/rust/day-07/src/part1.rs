@@ -1,18 +1,28 @@
+use aoc_core::parse::{char_grid, finish};
 use itertools::Itertools;
 
+use crate::ManifoldError;
+
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String> {
-    let mut diagram = input.lines().map(|line| {
+    let diagram = parse(input)?;
+    solve(&diagram)
+}
+
+pub fn solve(
+    diagram: &[Vec<char>],
+) -> miette::Result<String> {
+    let mut diagram = diagram.iter().map(|line| {
         std::iter::once('.')
-            .chain(line.chars())
+            .chain(line.iter().copied())
             .chain(std::iter::once('.'))
     });
     let initial_beam = diagram
         .next()
-        .unwrap()
+        .ok_or(ManifoldError::Empty)?
         .map(|char| if char == 'S' { '|' } else { ' ' })
         .collect::<String>();
-    let result = diagram.fold(
+    let (_beam, split_count) = diagram.fold(
         (initial_beam, 0),
         |(beam, split_count), diagram_line| {
             let mut local_split_count = 0;
@@ -20,95 +30,107 @@ pub fn process(input: &str) -> miette::Result<String> {
                 .chars()
                 .tuple_windows()
                 .zip(diagram_line.tuple_windows())
-                .map(
-                    |(
-                        (beam_l, beam_c, beam_r),
-                        (diag_l, diag_c, diag_r),
-                    )| 
-                    {
-                        match (
-                            (beam_l, beam_c, beam_r),
-                            (diag_l, diag_c, diag_r),
-                        ) {
-                            ( // beam already exists and is not split up
-                                (_, '|', _),
-                                (_, '.', _)
-                            ) => {
-                                // print!("|");
-                                '|'
-                            },
-                            ( // beam existed and got split up
-                                (_, '|', _),
-                                (_, '^', _)
-                            ) => {
-                                // print!("^");
-                                local_split_count += 1;
-                                ' '
-                            },
-                            ( // beam got split up on the lefthand side
-                                ('|', _, _),
-                                ('^', _, _)
-                            ) => {
-                                // print!("\\");
-                                '|'
-                            },
-                            ( // beam got split up on the righthand side
-                                (_, _, '|'),
-                                (_, _, '^')
-                            ) => {
-                                // print!("/");
-                                '|'
-                            },
-                            ( // beam did not exist, and did not get split up on either side (one of the prior arms wouldve matched)
-                                (_, ' ', _),
-                                (_, _, _)
-                            ) => {
-                                // print!(".");
-                                ' '
-                            },
-                            (
-                                (_, _, _),
-                                (_, _, _)
-                            ) => panic!("Situation not covered. Beam: [{}{}{}], Diagram: [{}{}{}]", beam_l, beam_c, beam_r, diag_l, diag_c, diag_r),
+                .map(|(beam, diagram)| {
+                    match (beam, diagram) {
+                        // beam existed and got split up
+                        ((_, '|', _), (_, '^', _)) => {
+                            local_split_count += 1;
+                            ' '
                         }
+                        // beam already exists and is not
+                        // split up (parse keeps the `S` on
+                        // the first line, so this is a `.`)
+                        ((_, '|', _), _) => '|',
+                        // beam got split up on the lefthand
+                        // side
+                        (('|', _, _), ('^', _, _)) => '|',
+                        // beam got split up on the
+                        // righthand side
+                        ((_, _, '|'), (_, _, '^')) => '|',
+                        // beam did not exist, and did not
+                        // get split up on either side
+                        _ => ' ',
                     }
-                )
+                })
                 .collect::<String>();
-            // println!(" new splits: {}, total_splits: {}", local_split_count, split_count);
-            let padded_resulting_beam = std::iter::once('.')
-                .chain(resulting_beam.chars())
-                .chain(std::iter::once('.'))
-                .collect::<String>();
-            (padded_resulting_beam, split_count + local_split_count)
+            let padded_resulting_beam =
+                std::iter::once('.')
+                    .chain(resulting_beam.chars())
+                    .chain(std::iter::once('.'))
+                    .collect::<String>();
+            (
+                padded_resulting_beam,
+                split_count + local_split_count,
+            )
         },
     );
 
-    Ok(result.1.to_string())
+    Ok(split_count.to_string())
+}
+
+/// The rows of the manifold's diagram, with the
+/// beam entering at the `S` on the first row.
+pub fn parse(
+    input: &str,
+) -> Result<Vec<Vec<char>>, ManifoldError> {
+    let diagram = finish(input, char_grid(".^S"))?;
+    for (line, row) in diagram.iter().enumerate().skip(1) {
+        if let Some(column) =
+            row.iter().position(|&cell| cell == 'S')
+        {
+            return Err(ManifoldError::MisplacedStart {
+                line: line + 1,
+                column: column + 1,
+            });
+        }
+    }
+    Ok(diagram)
 }
 
 #[cfg(test)]
 mod tests {
+    use rstest::rstest;
+
     use super::*;
 
+    aoc_core::example_tests!(part1);
+
     #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = ".......S.......
-...............
-.......^.......
-...............
-......^.^......
-...............
-.....^.^.^.....
-...............
-....^.^...^....
-...............
-...^.^...^.^...
-...............
-..^...^.....^..
-...............
-.^.^.^.^.^...^.
-...............";
-        assert_eq!("21", process(input)?);
-        Ok(())
+    fn test_misplaced_start() {
+        assert!(matches!(
+            parse("..S..\n.....\n...S."),
+            Err(ManifoldError::MisplacedStart {
+                line: 3,
+                column: 4
+            })
+        ));
+        let input = "..S..\n..S..";
+        for result in
+            [process(input), crate::part2::process(input)]
+        {
+            assert!(matches!(
+                result.unwrap_err().downcast(),
+                Ok(ManifoldError::MisplacedStart {
+                    line: 2,
+                    column: 3
+                })
+            ));
+        }
+    }
+
+    #[rstest]
+    #[case::empty("")]
+    #[case::truncated("..S..\n.....\n..^")]
+    #[case::garbage("..S..\n..#..")]
+    #[case::multibyte("..S..\n..\u{e9}..")]
+    fn test_malformed(#[case] input: &str) {
+        for result in
+            [process(input), crate::part2::process(input)]
+        {
+            assert!(matches!(
+                result.unwrap_err().downcast(),
+                Ok(ManifoldError::Parse(_))
+            ));
+        }
     }
 }
@@ -1,57 +1,60 @@
 use itertools::Itertools;
 
+use crate::{ManifoldError, part1::parse};
+
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String> {
-    let mut diagram = input.lines().map(|line| {
+    let diagram = parse(input)?;
+    solve(&diagram)
+}
+
+pub fn solve(
+    diagram: &[Vec<char>],
+) -> miette::Result<String> {
+    let mut diagram = diagram.iter().map(|line| {
         std::iter::once('.')
-            .chain(line.chars())
+            .chain(line.iter().copied())
             .chain(std::iter::once('.'))
     });
     let initial_beam = diagram
         .next()
-        .unwrap()
+        .ok_or(ManifoldError::Empty)?
         .map(|char| if char == 'S' { 1 } else { 0 })
         .collect::<Vec<u64>>();
-    let result = diagram.fold(
-        initial_beam,
-        |beam, diagram_line| {
+    let result = diagram
+        .try_fold(initial_beam, |beam, diagram_line| {
             let resulting_beam = beam
                 .into_iter()
                 .tuple_windows()
                 .zip(diagram_line.tuple_windows())
-                .map(
-                    |(
-                        (beam_l, beam_c, beam_r),
-                        (diag_l, diag_c, diag_r),
-                    )| 
-                    {
-                        match (
-                            (beam_l, beam_c, beam_r),
-                            (diag_l, diag_c, diag_r),
-                        ) {
-                            (
-                                (_, _, _),
-                                (_, '^', _)
-                            ) => 0,
-                            (
-                                (lb, cb, rb),
-                                (ls, '.', rs)
-                            ) => (if ls == '^' {lb} else {0}) + cb + (if rs == '^' {rb} else {0}),
-                            (
-                                (_, _, _),
-                                (_, _, _)
-                            ) => panic!("Situation not covered. Beam: [{}{}{}], Diagram: [{}{}{}]", beam_l, beam_c, beam_r, diag_l, diag_c, diag_r),
-                        }
+                .map(|(beam, diagram)| {
+                    match (beam, diagram) {
+                        ((_, _, _), (_, '^', _)) => Ok(0),
+                        // parse keeps the `S` on the first
+                        // line,
+                        // so the center is a `.`
+                        ((lb, cb, rb), (ls, _, rs)) => [
+                            (ls == '^').then_some(lb),
+                            Some(cb),
+                            (rs == '^').then_some(rb),
+                        ]
+                        .into_iter()
+                        .flatten()
+                        .try_fold(0u64, u64::checked_add)
+                        .ok_or(ManifoldError::Overflow),
                     }
-                )
-                .collect::<Vec<u64>>();
-            std::iter::once(0)
-                .chain(resulting_beam)
-                .chain(std::iter::once(0))
-                .collect::<Vec<u64>>()
-        },
-    )
-    .iter().sum::<u64>();
+                })
+                .collect::<Result<Vec<u64>, _>>()?;
+            Ok::<_, ManifoldError>(
+                std::iter::once(0)
+                    .chain(resulting_beam)
+                    .chain(std::iter::once(0))
+                    .collect::<Vec<u64>>(),
+            )
+        })?
+        .into_iter()
+        .try_fold(0u64, u64::checked_add)
+        .ok_or(ManifoldError::Overflow)?;
 
     Ok(result.to_string())
 }
@@ -60,25 +63,5 @@ pub fn process(input: &str) -> miette::Result<String> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_process() -> miette::Result<()> {
-        let input = ".......S.......
-...............
-.......^.......
-...............
-......^.^......
-...............
-.....^.^.^.....
-...............
-....^.^...^....
-...............
-...^.^...^.^...
-...............
-..^...^.....^..
-...............
-.^.^.^.^.^...^.
-...............";
-        assert_eq!("40", process(input)?);
-        Ok(())
-    }
+    aoc_core::example_tests!(part2);
 }
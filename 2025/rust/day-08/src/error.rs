@@ -0,0 +1,20 @@
+use aoc_core::parse::ParseError;
+
+/// Why the junction boxes can't be wired up.
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum PlaygroundError {
+    /// A line that isn't a box like
+    /// `162,817,812`.
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Parse(#[from] ParseError),
+    #[error(
+        "found {found} junction boxes, connecting them \
+         takes at least 2"
+    )]
+    #[diagnostic(
+        code(day_08::too_few_boxes),
+        help("the input should list a box on every line")
+    )]
+    TooFewBoxes { found: usize },
+}
@@ -1,2 +1,36 @@
+use aoc_core::Solution;
+
+pub use crate::error::PlaygroundError;
+use crate::part1::JunctionBox;
+
+mod error;
 pub mod part1;
 pub mod part2;
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2025;
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Playground";
+
+    type Parsed<'a> = Vec<JunctionBox>;
+
+    fn parse(
+        input: &str,
+    ) -> miette::Result<Vec<JunctionBox>> {
+        Ok(part1::parse(input)?)
+    }
+
+    fn part1(
+        boxes: &Vec<JunctionBox>,
+    ) -> miette::Result<String> {
+        part1::solve(boxes)
+    }
+
+    fn part2(
+        boxes: &Vec<JunctionBox>,
+    ) -> miette::Result<String> {
+        part2::solve(boxes)
+    }
+}
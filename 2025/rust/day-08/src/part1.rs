@@ -1,155 +1,366 @@
-use std::{
-    collections::{HashMap, HashSet},
-    rc::Rc,
-    str::FromStr,
-    sync::Mutex,
-};
+use std::{collections::BinaryHeap, str::FromStr};
 
+use aoc_collections::DisjointSet;
+use aoc_core::parse::{
+    IResult, Span, finish, lines, signed,
+};
 use itertools::Itertools;
-use miette::{IntoDiagnostic, miette};
+use nom::{Parser, character::complete::char};
+
+use crate::PlaygroundError;
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String> {
-    let boxes: Vec<JunctionBox> = input
-        .lines()
-        .map(JunctionBox::from_str)
-        .collect::<miette::Result<Vec<JunctionBox>>>()?;
-    let mut circuit_membership: HashMap<
-        &JunctionBox,
-        Rc<Mutex<HashSet<&JunctionBox>>>,
-    > = HashMap::with_capacity(boxes.len());
-    let circuits: Vec<Rc<Mutex<HashSet<&JunctionBox>>>> =
-        boxes
-            .iter()
-            .map(|junction_box| {
-                let set =
-                    Rc::new(Mutex::new(HashSet::new()));
-                set.lock().unwrap().insert(junction_box);
-                circuit_membership
-                    .insert(junction_box, set.clone());
-                set
-            })
-            .collect();
-    let distances = boxes
-        .iter()
-        .tuple_combinations()
-        .map(|(box1, box2)| {
-            (box1, box2, box1.distance_from(box2))
-        })
-        .sorted_by(|a, b| a.2.total_cmp(&b.2));
-
-    distances.take(1000).for_each(
-        |(box1, box2, _distance)| {
-            let box1_rc = circuit_membership
-                .get(box1)
-                .expect("expected box to be a member of a circuit")
-                .clone();
-
-            let box2_rc = circuit_membership
-                .get(box2)
-                .expect("expected box to be a member of a circuit")
-                .clone();
-
-            if Rc::ptr_eq(&box1_rc, &box2_rc) {
-                return;
-            }
+    process_with(input, CircuitConfig::default())
+}
 
-            let moved_boxes: Vec<&JunctionBox> = {
-                let mut box2_set = box2_rc.lock().unwrap();
-                box2_set.drain().collect()
-            };
+pub fn process_with(
+    input: &str,
+    config: CircuitConfig,
+) -> miette::Result<String> {
+    let boxes = parse(input)?;
+    solve_with(&boxes, config)
+}
 
-            {
-                let mut box1_set = box1_rc.lock().unwrap();
-                box1_set
-                    .extend(moved_boxes.iter().copied());
-            }
+pub fn parse(
+    input: &str,
+) -> Result<Vec<JunctionBox>, PlaygroundError> {
+    Ok(finish(
+        input,
+        lines(JunctionBox::parse),
+    )?)
+}
 
-            for b in moved_boxes {
-                circuit_membership
-                    .insert(b, box1_rc.clone());
-            }
-        },
-    );
+/// How the boxes get connected and what the
+/// answer is made of.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CircuitConfig {
+    /// how many of the closest pairs get
+    /// connected
+    pub connections: usize,
+    /// how many of the largest circuits get
+    /// multiplied
+    pub top_k: usize,
+}
 
+impl Default for CircuitConfig {
+    /// The puzzle's: 1000 connections and the 3
+    /// largest circuits. The example only makes
+    /// 10 connections.
+    fn default() -> Self {
+        CircuitConfig {
+            connections: 1000,
+            top_k: 3,
+        }
+    }
+}
+
+/// The `k` closest pairs of boxes as indices into
+/// `boxes`, closest first.
+///
+/// Walks all n(n-1)/2 pairs but only keeps the
+/// `k` closest seen so far in a max-heap, so the
+/// memory is O(k) and the time O(n² log k).
+/// Pairs at the same distance are ordered by
+/// their indices.
+pub fn closest_pairs(
+    boxes: &[JunctionBox],
+    k: usize,
+) -> Vec<(usize, usize)> {
+    let mut closest = BinaryHeap::new();
+    for (a, b) in (0..boxes.len()).tuple_combinations() {
+        let pair = (
+            boxes[a].distance_squared(&boxes[b]),
+            a,
+            b,
+        );
+        if closest.len() < k {
+            closest.push(pair);
+        } else if let Some(mut farthest) =
+            closest.peek_mut()
+            && pair < *farthest
+        {
+            *farthest = pair;
+        }
+    }
+    closest
+        .into_sorted_vec()
+        .into_iter()
+        .map(|(_distance, a, b)| (a, b))
+        .collect()
+}
+
+pub fn solve(
+    boxes: &[JunctionBox],
+) -> miette::Result<String> {
+    solve_with(boxes, CircuitConfig::default())
+}
+
+pub fn solve_with(
+    boxes: &[JunctionBox],
+    config: CircuitConfig,
+) -> miette::Result<String> {
+    let circuits =
+        Circuits::connect(boxes, config.connections);
     let result = circuits
-        .iter()
-        .map(|circuit| circuit.lock().unwrap().len())
-        .sorted_by(|a, b: &usize| b.cmp(a))
-        .take(3)
+        .sizes()
+        .into_iter()
+        .take(config.top_k)
         .product::<usize>();
 
     Ok(result.to_string())
 }
 
+/// The circuits formed by connecting the closest
+/// pairs of boxes.
+#[derive(Clone, Debug)]
+pub struct Circuits<'a> {
+    boxes: &'a [JunctionBox],
+    set: DisjointSet,
+    last_merge: Option<(usize, usize)>,
+}
+
+impl<'a> Circuits<'a> {
+    /// Connects the `connections` closest pairs,
+    /// closest first.
+    pub fn connect(
+        boxes: &'a [JunctionBox],
+        connections: usize,
+    ) -> Self {
+        let mut set = DisjointSet::new(boxes.len());
+        let mut last_merge = None;
+        for (a, b) in closest_pairs(boxes, connections) {
+            if set.union(a, b) {
+                last_merge = Some((a, b));
+            }
+        }
+        Circuits {
+            boxes,
+            set,
+            last_merge,
+        }
+    }
+
+    /// The number of boxes in every circuit,
+    /// largest first. A box that isn't connected
+    /// is a circuit of its own.
+    pub fn sizes(&self) -> Vec<usize> {
+        self.set
+            .component_sizes()
+            .sorted_by(|a, b| b.cmp(a))
+            .collect()
+    }
+
+    /// The boxes in the same circuit as the box
+    /// at `index`, itself included, in input
+    /// order.
+    ///
+    /// # Panics
+    ///
+    /// When `index` is out of bounds.
+    pub fn circuit_of(
+        &mut self,
+        index: usize,
+    ) -> Vec<&'a JunctionBox> {
+        let root = self.set.find(index);
+        (0..self.boxes.len())
+            .filter(|&other| self.set.find(other) == root)
+            .map(|other| &self.boxes[other])
+            .collect()
+    }
+
+    /// The last connection that joined two
+    /// separate circuits. Once every box is in a
+    /// single circuit, that is the connection
+    /// which merged the last two.
+    pub fn last_merge(
+        &self,
+    ) -> Option<(&'a JunctionBox, &'a JunctionBox)> {
+        self.last_merge
+            .map(|(a, b)| (&self.boxes[a], &self.boxes[b]))
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Hash)]
-struct JunctionBox {
-    x: i128,
-    y: i128,
-    z: i128,
+pub struct JunctionBox {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
 }
 
 impl JunctionBox {
-    fn distance_from(&self, other: &JunctionBox) -> f64 {
+    /// A box like `162,817,812`. The coordinates
+    /// have to fit in an `i32`, which keeps their
+    /// squared distances within a `u128` and the
+    /// product of two of them within an `i64`.
+    pub fn parse(input: Span<'_>) -> IResult<'_, Self> {
+        (
+            signed::<i32>,
+            char(','),
+            signed::<i32>,
+            char(','),
+            signed::<i32>,
+        )
+            .map(|(x, _, y, _, z)| JunctionBox {
+                x: x.into(),
+                y: y.into(),
+                z: z.into(),
+            })
+            .parse(input)
+    }
+
+    /// The squared euclidean distance, which
+    /// orders pairs the same as the distance
+    /// itself without leaving the integers.
+    pub fn distance_squared(
+        &self,
+        other: &JunctionBox,
+    ) -> u128 {
         let JunctionBox { x, y, z } = other;
-        let dist_x = (self.x - x).pow(2) as f64;
-        let dist_y = (self.y - y).pow(2) as f64;
-        let dist_z = (self.z - z).pow(2) as f64;
-        (dist_x + dist_y + dist_z).powf(0.5)
+        [(self.x, *x), (self.y, *y), (self.z, *z)]
+            .into_iter()
+            .map(|(a, b)| u128::from(a.abs_diff(b)).pow(2))
+            .sum()
     }
 }
 
 impl FromStr for JunctionBox {
-    type Err = miette::Report;
+    type Err = PlaygroundError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut split = s.split(",");
-        let x = split.next().ok_or(miette!(
-            "expected a non-empty string"
-        ))?;
-        let y = split
-            .next()
-            .ok_or(miette!("expected 2 comma's found 0"))?;
-        let z = split
-            .next()
-            .ok_or(miette!("expected 2 comma's found 1"))?;
-
-        let x = x.parse::<i128>().into_diagnostic()?;
-        let y = y.parse::<i128>().into_diagnostic()?;
-        let z = z.parse::<i128>().into_diagnostic()?;
-
-        Ok(JunctionBox { x, y, z })
+        Ok(finish(s, JunctionBox::parse)?)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use rstest::rstest;
+
     use super::*;
 
+    const EXAMPLE: CircuitConfig = CircuitConfig {
+        connections: 10,
+        top_k: 3,
+    };
+
     #[test]
-    #[ignore]
     fn test_process() -> miette::Result<()> {
-        let input = "162,817,812
-57,618,57
-906,360,560
-592,479,940
-352,342,300
-466,668,158
-542,29,236
-431,825,988
-739,650,466
-52,470,668
-216,146,977
-819,987,18
-117,168,530
-805,96,715
-346,949,466
-970,615,88
-941,993,340
-862,61,35
-984,92,344
-425,690,689";
-        assert_eq!("40", process(input)?);
+        let input = include_str!("../examples/part1-1.txt");
+        assert_eq!("40", process_with(input, EXAMPLE)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_circuits() -> miette::Result<()> {
+        let boxes =
+            parse(include_str!("../examples/part1-1.txt"))?;
+        let mut circuits =
+            Circuits::connect(&boxes, EXAMPLE.connections);
+        assert_eq!(
+            circuits.sizes(),
+            [5, 4, 2, 2, 1, 1, 1, 1, 1, 1, 1]
+        );
+        let circuit = circuits.circuit_of(0);
+        assert_eq!(
+            circuit
+                .iter()
+                .map(|b| (b.x, b.y, b.z))
+                .collect::<Vec<_>>(),
+            [
+                (162, 817, 812),
+                (431, 825, 988),
+                (346, 949, 466),
+                (425, 690, 689),
+            ]
+        );
+        let (a, b) = circuits.last_merge().unwrap();
+        assert_eq!((a.x, b.x), (906, 984));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::none(0)]
+    #[case::some(10)]
+    #[case::all(190)]
+    #[case::more_than_all(1000)]
+    fn test_closest_pairs(
+        #[case] k: usize,
+    ) -> miette::Result<()> {
+        let boxes =
+            parse(include_str!("../examples/part1-1.txt"))?;
+        // every pair sorted, ties broken by index
+        let sorted = (0..boxes.len())
+            .tuple_combinations()
+            .sorted_by_key(|&(a, b)| {
+                (
+                    boxes[a].distance_squared(&boxes[b]),
+                    a,
+                    b,
+                )
+            })
+            .take(k)
+            .collect::<Vec<_>>();
+        assert_eq!(closest_pairs(&boxes, k), sorted);
+        Ok(())
+    }
+
+    #[test]
+    fn test_last_merge() -> miette::Result<()> {
+        let boxes =
+            parse(include_str!("../examples/part1-1.txt"))?;
+        let circuits =
+            Circuits::connect(&boxes, usize::MAX);
+        assert_eq!(circuits.sizes(), [20]);
+        let (a, b) = circuits.last_merge().unwrap();
+        assert_eq!(a.x * b.x, 25272);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error =
+            parse("162,817,812\n57,618\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected `,` at line 2, column 7"
+        );
+        assert!(
+            "1,2,99999999999"
+                .parse::<JunctionBox>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_far_apart() -> miette::Result<()> {
+        let a = "-2147483648,-2147483648,-2147483648"
+            .parse::<JunctionBox>()?;
+        let b = "2147483647,2147483647,2147483647"
+            .parse::<JunctionBox>()?;
+        let span = u128::from(u32::MAX);
+        assert_eq!(a.distance_squared(&b), 3 * span * span);
+        // one closer on a single axis is still closer
+        let c = "2147483647,2147483647,2147483646"
+            .parse::<JunctionBox>()?;
+        assert!(
+            a.distance_squared(&c) < a.distance_squared(&b)
+        );
         Ok(())
     }
+
+    #[rstest]
+    #[case::empty("")]
+    #[case::truncated("162,817,812\n57,618,57\n906,3")]
+    #[case::garbage("162,817,812\nhello")]
+    #[case::blank_line("162,817,812\n\n57,618,57")]
+    fn test_malformed(#[case] input: &str) {
+        for result in
+            [process(input), crate::part2::process(input)]
+        {
+            assert!(matches!(
+                result.unwrap_err().downcast(),
+                Ok(PlaygroundError::Parse(_))
+            ));
+        }
+    }
 }
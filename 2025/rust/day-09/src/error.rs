@@ -0,0 +1,43 @@
+use aoc_core::parse::ParseError;
+
+/// Why no rectangle can be laid out on the
+/// theater's floor.
+#[derive(Debug, thiserror::Error, miette::Diagnostic)]
+pub enum TheaterError {
+    /// A line that isn't a tile like `7,1`.
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Parse(#[from] ParseError),
+    #[error(
+        "found {found} red tiles, a rectangle needs \
+         two corners"
+    )]
+    #[diagnostic(
+        code(day_09::too_few_tiles),
+        help(
+            "the input should list a red tile on every line"
+        )
+    )]
+    TooFewTiles { found: usize },
+    #[error(
+        "the red tile on line {line} and the next one \
+         share neither a row nor a column"
+    )]
+    #[diagnostic(
+        code(day_09::diagonal_edge),
+        help(
+            "every red tile is connected to the next one, \
+             and the last to the first, by a straight line \
+             of green tiles"
+        )
+    )]
+    DiagonalEdge { line: usize },
+    #[error(
+        "no rectangle fits inside the red and green tiles"
+    )]
+    #[diagnostic(
+        code(day_09::no_rectangle),
+        help("a rectangle needs at least two red tiles")
+    )]
+    NoRectangle,
+}
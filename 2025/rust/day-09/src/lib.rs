@@ -1,2 +1,34 @@
+use aoc_core::Solution;
+
+pub use crate::error::TheaterError;
+use crate::part1::Tile;
+
+mod error;
 pub mod part1;
 pub mod part2;
+
+pub struct Day;
+
+impl Solution for Day {
+    const YEAR: u16 = 2025;
+    const DAY: u8 = 9;
+    const TITLE: &'static str = "Movie Theater";
+
+    type Parsed<'a> = Vec<Tile>;
+
+    fn parse(input: &str) -> miette::Result<Vec<Tile>> {
+        Ok(part1::parse(input)?)
+    }
+
+    fn part1(
+        corners: &Vec<Tile>,
+    ) -> miette::Result<String> {
+        part1::solve(corners)
+    }
+
+    fn part2(
+        corners: &Vec<Tile>,
+    ) -> miette::Result<String> {
+        part2::solve(corners)
+    }
+}
@@ -1,28 +1,67 @@
+use aoc_core::parse::{IResult, Span, finish, lines, unsigned};
 use itertools::Itertools;
+use nom::{
+    Parser, character::complete::char, sequence::separated_pair,
+};
+
+use crate::TheaterError;
 
 #[tracing::instrument]
 pub fn process(input: &str) -> miette::Result<String> {
-    let result = input
-        .lines()
-        .map(|line| {
-            let (x,y) = line.split_once(',').unwrap();
-            (x.parse::<u64>().unwrap(), y.parse::<u64>().unwrap())
-        })
+    let corners = parse(input)?;
+    solve(&corners)
+}
+
+pub fn parse(input: &str) -> Result<Vec<Tile>, TheaterError> {
+    Ok(finish(input, lines(Tile::parse))?)
+}
+
+pub fn solve(corners: &[Tile]) -> miette::Result<String> {
+    let result = corners
+        .iter()
         .tuple_combinations()
-        .map(|((x1,y1),(x2,y2))| area((x1, y1), (x2, y2)))
-        // .sorted_by(|a, b| b.cmp(&a))
+        .map(|(&a, &b)| area(a, b))
         .max()
-        .unwrap();
+        .ok_or(TheaterError::TooFewTiles {
+            found: corners.len(),
+        })?;
 
     Ok(result.to_string())
 }
 
-pub fn area((x1, y1): (u64, u64), (x2, y2): (u64, u64)) -> u64 {
-    ((x1.max(x2) + 1) - x1.min(x2)) * ((y1.max(y2) + 1) - y1.min(y2))
+/// The number of tiles in the rectangle with
+/// corners `a` and `b`. Coordinates fit in a `u32`,
+/// but a rectangle spanning all of them holds one
+/// tile more than a `u64` can count.
+pub fn area(a: Tile, b: Tile) -> u128 {
+    (a.x.abs_diff(b.x) as u128 + 1)
+        * (a.y.abs_diff(b.y) as u128 + 1)
+}
+
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+pub struct Tile {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Tile {
+    pub fn new(x: usize, y: usize) -> Self {
+        Tile { x, y }
+    }
+
+    /// A tile like `7,1`. The coordinates have to
+    /// fit in a `u32`, so areas fit in a `u128`.
+    pub fn parse(input: Span<'_>) -> IResult<'_, Tile> {
+        separated_pair(unsigned::<u32>, char(','), unsigned::<u32>)
+            .map(|(x, y)| Tile::new(x as usize, y as usize))
+            .parse(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use rstest::rstest;
+
     use super::*;
 
     #[test]
@@ -38,4 +77,46 @@ mod tests {
         assert_eq!("50", process(input)?);
         Ok(())
     }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse("7,1\n11;1\n").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected `,` at line 2, column 3"
+        );
+    }
+
+    #[test]
+    fn test_area() {
+        assert_eq!(area(Tile::new(2, 5), Tile::new(11, 1)), 50);
+        let far = Tile::new(u32::MAX as usize, u32::MAX as usize);
+        assert_eq!(area(Tile::new(0, 0), far), 1 << 64);
+    }
+
+    #[rstest]
+    #[case::empty("")]
+    #[case::truncated("7,1\n11,1\n11,")]
+    #[case::garbage("7,1\n11,1\n\u{1F3AC}")]
+    #[case::too_large("7,1\n99999999999,1")]
+    fn test_malformed(#[case] input: &str) {
+        for result in [process(input), crate::part2::process(input)] {
+            assert!(matches!(
+                result.unwrap_err().downcast(),
+                Ok(TheaterError::Parse(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_one_tile() {
+        assert!(matches!(
+            process("7,1").unwrap_err().downcast(),
+            Ok(TheaterError::TooFewTiles { found: 1 })
+        ));
+        assert!(matches!(
+            crate::part2::process("7,1").unwrap_err().downcast(),
+            Ok(TheaterError::NoRectangle)
+        ));
+    }
 }
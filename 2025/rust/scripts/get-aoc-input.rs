@@ -3,44 +3,219 @@
 [package]
 edition = "2024"
 [dependencies]
-clap = { version = "4.5", features = ["derive"] }
+aoc = { path = "../aoc" }
+clap = { version = "4.5", features = ["derive", "env"] }
 nom = "8"
-reqwest = { version = "0.12", features=["blocking"] }
+sha2 = "0.10"
+thiserror = "2"
 ---
 
-use clap::{error::ErrorKind, CommandFactory, Parser};
+use aoc::{
+    client::{
+        BASE_URL_VAR, Client, ClientError, DEFAULT_BASE_URL,
+    },
+    puzzle,
+};
+use clap::{CommandFactory, Parser, error::ErrorKind};
 use nom::{
-    bytes::complete::tag, character::complete,
-    sequence::preceded, IResult, Parser as NomParser,
+    IResult, Parser as NomParser,
+    bytes::complete::tag,
+    character::complete,
+    combinator::{all_consuming, opt},
+    sequence::preceded,
+};
+use sha2::{Digest, Sha256};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::ExitCode,
 };
-use reqwest::{blocking::Client, header::COOKIE};
-use std::fs::File;
-use std::io::Write;
-use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[clap(version)]
 struct Args {
     /// day is expected to be formatted as
     /// `day-01` to match all other commands in
-    /// the repo
+    /// the repo, a plain `1` works too
     #[clap(short, long)]
     day: String,
+    /// defaults to the year directory the repo
+    /// lives in, e.g. `2025` for `2025/rust`
+    #[clap(short, long)]
+    year: Option<u16>,
     /// a way to pass in the justfile directory
     /// so that we're always in the root without
     /// doing any shenanigans
     #[clap(long)]
     current_working_directory: PathBuf,
+    /// only read inputs from the cache, never
+    /// hit adventofcode.com
+    #[clap(long)]
+    offline: bool,
+    /// where fetched inputs are kept, defaults to
+    /// `$XDG_CACHE_HOME/aoc` or `~/.cache/aoc`
+    #[clap(long, env = "AOC_CACHE_DIR")]
+    cache_dir: Option<PathBuf>,
+    /// point this at a stand-in server to try
+    /// things out without hitting the real site
+    #[clap(
+        long,
+        env = BASE_URL_VAR,
+        default_value = DEFAULT_BASE_URL
+    )]
+    base_url: String,
+}
+
+#[derive(Debug, thiserror::Error)]
+enum Error {
+    #[error(
+        "SESSION isn't set, it's needed to fetch {what}"
+    )]
+    NoSession { what: &'static str },
+    #[error(
+        "--offline: no cached input for {year} \
+         day-{day:02} in {}",
+        dir.display()
+    )]
+    NotCached { year: u16, day: u8, dir: PathBuf },
+    #[error(
+        "{} holds inputs for {year} day-{day:02} from \
+         {count} accounts, set SESSION to pick one",
+        dir.display()
+    )]
+    AmbiguousCache {
+        year: u16,
+        day: u8,
+        dir: PathBuf,
+        count: usize,
+    },
+    #[error("failed to write {}", path.display())]
+    Write { path: PathBuf, source: io::Error },
+    #[error(transparent)]
+    Client(#[from] ClientError),
+}
+
+fn parse_day(input: &str) -> IResult<&str, u8> {
+    all_consuming(preceded(opt(tag("day-")), complete::u8))
+        .parse(input)
+}
+
+fn parse_year(input: &str) -> IResult<&str, u16> {
+    all_consuming(complete::u16).parse(input)
+}
+
+/// The closest directory named like a year, e.g.
+/// `2025` for `advent-of-code/2025/rust`.
+fn year_from_path(path: &Path) -> Option<u16> {
+    path.ancestors()
+        .filter_map(|dir| dir.file_name()?.to_str())
+        .find_map(|name| Some(parse_year(name).ok()?.1))
+        .filter(|year| *year >= 2015)
+}
+
+fn default_cache_dir() -> PathBuf {
+    std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var_os("HOME").map(|home| {
+                PathBuf::from(home).join(".cache")
+            })
+        })
+        .unwrap_or_else(std::env::temp_dir)
+        .join("aoc")
+}
+
+/// The first 8 bytes of the sha256 of `text`, in
+/// hex.
+fn short_hash(text: &str) -> String {
+    Sha256::digest(text.as_bytes())[..8]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// A stand-in server hands out its own inputs, so
+/// each base url gets a directory of its own.
+fn cache_year_dir(
+    cache_dir: &Path,
+    base_url: &str,
+    year: u16,
+) -> PathBuf {
+    let server = short_hash(base_url.trim_end_matches('/'));
+    cache_dir.join(server).join(year.to_string())
+}
+
+/// Inputs differ per account, so the key includes
+/// a hash of the session (never the session
+/// itself).
+fn cache_path(
+    cache_dir: &Path,
+    base_url: &str,
+    year: u16,
+    day: u8,
+    session: &str,
+) -> PathBuf {
+    let account = short_hash(session);
+    cache_year_dir(cache_dir, base_url, year)
+        .join(format!("day-{day:02}-{account}.txt"))
+}
+
+/// The cached inputs for `day` of every account,
+/// for looking one up without a session.
+fn cached_inputs(dir: &Path, day: u8) -> Vec<PathBuf> {
+    let prefix = format!("day-{day:02}-");
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| {
+                    name.starts_with(&prefix)
+                        && name.ends_with(".txt")
+                })
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), Error> {
+    fs::write(path, contents).map_err(|source| {
+        Error::Write {
+            path: path.to_path_buf(),
+            source,
+        }
+    })
+}
+
+/// Fetching needs the session, reading the cache
+/// doesn't, so a missing one is only an error
+/// here.
+fn session(what: &'static str) -> Result<String, Error> {
+    std::env::var("SESSION")
+        .map_err(|_| Error::NoSession { what })
 }
 
-fn parse_day(input: &str) -> IResult<&str, u32> {
-    preceded(tag("day-"), complete::u32).parse(input)
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            let mut source =
+                std::error::Error::source(&error);
+            while let Some(cause) = source {
+                eprintln!("  caused by: {cause}");
+                source = cause.source();
+            }
+            ExitCode::FAILURE
+        }
+    }
 }
 
-fn main() -> Result<(), reqwest::Error> {
-    let session = std::env::var("SESSION")
-        .expect("should have a session token set");
-    let args = Args::parse();
+fn run(args: Args) -> Result<(), Error> {
     let Ok((_, day)) = parse_day(&args.day) else {
         let mut cmd = Args::command();
         cmd.error(
@@ -52,32 +227,209 @@ fn main() -> Result<(), reqwest::Error> {
         )
         .exit()
     };
+    let Some(year) = args.year.or_else(|| {
+        year_from_path(&args.current_working_directory)
+    }) else {
+        let mut cmd = Args::command();
+        cmd.error(
+            ErrorKind::MissingRequiredArgument,
+            format!(
+                "no year directory above `{}`, pass --year",
+                args.current_working_directory.display()
+            ),
+        )
+        .exit()
+    };
+    let day_dir = format!("day-{day:02}");
+
+    let cache_dir =
+        args.cache_dir.unwrap_or_else(default_cache_dir);
+    let year_dir =
+        cache_year_dir(&cache_dir, &args.base_url, year);
+    let cached = match std::env::var("SESSION") {
+        Ok(session) => vec![cache_path(
+            &cache_dir,
+            &args.base_url,
+            year,
+            day,
+            &session,
+        )],
+        // without a session any account's input will
+        // do, as long as there is only one
+        Err(_) => cached_inputs(&year_dir, day),
+    };
+    if cached.len() > 1 {
+        return Err(Error::AmbiguousCache {
+            year,
+            day,
+            dir: year_dir,
+            count: cached.len(),
+        });
+    }
+    let cached = cached.first().and_then(|path| {
+        Some((path, fs::read_to_string(path).ok()?))
+    });
+
+    let input_data = match cached {
+        Some((cache_file, cached)) => {
+            println!(
+                "using cached {}",
+                cache_file.display()
+            );
+            cached
+        }
+        None if args.offline => {
+            return Err(Error::NotCached {
+                year,
+                day,
+                dir: year_dir,
+            });
+        }
+        None => {
+            let session = session("the input")?;
+            let fetched =
+                Client::new(&args.base_url, &session)
+                    .input(year, day)?;
+            let cache_file = cache_path(
+                &cache_dir,
+                &args.base_url,
+                year,
+                day,
+                &session,
+            );
+            fs::create_dir_all(&year_dir).map_err(
+                |source| Error::Write {
+                    path: year_dir.clone(),
+                    source,
+                },
+            )?;
+            write(&cache_file, &fetched)?;
+            println!("cached {}", cache_file.display());
+            fetched
+        }
+    };
+
+    let file_path = args
+        .current_working_directory
+        .join(&day_dir)
+        .join("input.txt");
+    write(&file_path, &input_data)?;
+    println!("wrote {}", file_path.display());
 
-    let url = format!(
-        "https://adventofcode.com/2025/day/{day}/input"
-    );
-    println!("sending to `{}`", url);
-
-    let client = Client::new();
-    let input_data = client
-        .get(url)
-        .header(COOKIE, format!("session={session}"))
-        .send()?
-        .text()?;
-
-    for filename in ["input1.txt", "input2.txt"] {
-        let file_path = args
-            .current_working_directory
-            .join(&args.day)
-            .join(filename);
-        let mut file = File::create(&file_path)
-            .expect("should be able to create a file");
-
-        file.write_all(input_data.as_bytes()).expect(
-            "should be able to write to input file",
+    let puzzle_path = args
+        .current_working_directory
+        .join(&day_dir)
+        .join("PUZZLE.md");
+    let puzzle = fs::read_to_string(&puzzle_path)
+        .unwrap_or_default();
+    if puzzle.contains("--- Part Two ---") {
+        println!("{} is complete", puzzle_path.display());
+    } else if args.offline {
+        println!(
+            "--offline: not fetching the puzzle description"
         );
-        println!("wrote {}", file_path.display());
+    } else {
+        // the page shows part 2 only once part 1 is
+        // solved, so it is never cached
+        let session = session("the puzzle description")?;
+        let page = Client::new(&args.base_url, &session)
+            .page(year, day)?;
+        match puzzle::markdown(&page, &args.base_url) {
+            Some(markdown) => {
+                write(&puzzle_path, &markdown)?;
+                println!("wrote {}", puzzle_path.display());
+            }
+            None => eprintln!(
+                "no puzzle description found on the page"
+            ),
+        }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_path() {
+        let dir = Path::new("cache");
+        let real = cache_path(
+            dir,
+            "https://adventofcode.com",
+            2025,
+            7,
+            "cookie",
+        );
+        assert!(real.starts_with(dir));
+        assert!(real.ends_with(format!(
+            "2025/day-07-{}.txt",
+            short_hash("cookie")
+        )));
+        assert_eq!(
+            real,
+            cache_path(
+                dir,
+                "https://adventofcode.com/",
+                2025,
+                7,
+                "cookie"
+            )
+        );
+        assert_ne!(
+            real,
+            cache_path(
+                dir,
+                "http://localhost:8000",
+                2025,
+                7,
+                "cookie"
+            )
+        );
+        assert_ne!(
+            real,
+            cache_path(
+                dir,
+                "https://adventofcode.com",
+                2025,
+                7,
+                "other"
+            )
+        );
+    }
+
+    #[test]
+    fn test_cached_inputs() {
+        let dir = std::env::temp_dir().join(format!(
+            "get-aoc-input-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        for name in [
+            "day-07-aaaa.txt",
+            "day-07-bbbb.txt",
+            "day-08-aaaa.txt",
+            "day-07-aaaa.tmp",
+        ] {
+            fs::write(dir.join(name), "").unwrap();
+        }
+        let day_07 = cached_inputs(&dir, 7);
+        let day_08 = cached_inputs(&dir, 8);
+        let day_09 = cached_inputs(&dir, 9);
+        let missing =
+            cached_inputs(&dir.join("missing"), 7);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            day_07,
+            [
+                dir.join("day-07-aaaa.txt"),
+                dir.join("day-07-bbbb.txt")
+            ]
+        );
+        assert_eq!(day_08, [dir.join("day-08-aaaa.txt")]);
+        assert!(day_09.is_empty());
+        assert!(missing.is_empty());
+    }
+}
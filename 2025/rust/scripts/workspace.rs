@@ -0,0 +1,564 @@
+#!/usr/bin/env -S cargo +nightly -Zscript
+---cargo
+[package]
+edition = "2024"
+[dependencies]
+clap = { version = "4.5", features = ["derive"] }
+toml_edit = "0.22"
+---
+
+use clap::{Parser, Subcommand};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use toml_edit::{
+    Array, DocumentMut, InlineTable, Item, Table, Value,
+};
+
+#[derive(Parser, Debug)]
+#[clap(version)]
+struct Args {
+    /// a way to pass in the justfile directory
+    /// so that we're always in the root without
+    /// doing any shenanigans
+    #[clap(long)]
+    current_working_directory: PathBuf,
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// list every `day-NN` directory as a
+    /// workspace member, and nothing else, and
+    /// register them with the aoc runner
+    Sync,
+    /// report day directories missing from the
+    /// workspace or with a mismatched bench name
+    Check,
+}
+
+fn is_day(name: &str) -> bool {
+    name.strip_prefix("day-").is_some_and(|number| {
+        number.len() == 2
+            && number.chars().all(|ch| ch.is_ascii_digit())
+    })
+}
+
+/// The `day-NN` directories holding a crate,
+/// sorted.
+fn day_dirs(workspace: &Path) -> Vec<String> {
+    let mut days = fs::read_dir(workspace)
+        .expect("should be able to list the workspace")
+        .filter_map(|entry| {
+            entry.ok()?.file_name().into_string().ok()
+        })
+        .filter(|name| {
+            is_day(name)
+                && workspace
+                    .join(name)
+                    .join("Cargo.toml")
+                    .is_file()
+        })
+        .collect::<Vec<_>>();
+    days.sort();
+    days
+}
+
+fn member_names(members: &Array) -> Vec<&str> {
+    members.iter().filter_map(Value::as_str).collect()
+}
+
+/// Replaces the `day-` entries, globs like
+/// `day-*` included, with `days`, keeping the
+/// other members and laying the list out one per
+/// line. Returns whether anything changed.
+fn sync_members(
+    members: &mut Array,
+    days: &[String],
+) -> bool {
+    let mut names = member_names(members)
+        .into_iter()
+        .filter(|name| !name.starts_with("day-"))
+        .map(str::to_string)
+        .chain(days.iter().cloned())
+        .collect::<Vec<_>>();
+    names.sort();
+    if names == member_names(members) {
+        return false;
+    }
+    members.clear();
+    for name in names {
+        let mut value = Value::from(name);
+        value.decor_mut().set_prefix("\n    ");
+        members.push_formatted(value);
+    }
+    members.set_trailing_comma(true);
+    members.set_trailing("\n");
+    true
+}
+
+/// The crate name a `day-NN` package is used by.
+fn crate_name(day: &str) -> String {
+    day.replace('-', "_")
+}
+
+/// Points the runner's `day-NN` dependencies at
+/// `days`, keeping its other dependencies.
+/// Returns whether anything changed.
+fn sync_dependencies(
+    dependencies: &mut Table,
+    days: &[String],
+) -> bool {
+    let stale = dependencies
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .filter(|name| is_day(name) && !days.contains(name))
+        .collect::<Vec<_>>();
+    for name in &stale {
+        dependencies.remove(name);
+    }
+    let mut changed = !stale.is_empty();
+    for day in days {
+        if !dependencies.contains_key(day) {
+            let mut dependency = InlineTable::new();
+            dependency
+                .insert("path", format!("../{day}").into());
+            dependencies.insert(
+                day,
+                Item::Value(Value::InlineTable(dependency)),
+            );
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// The line of `SOLUTIONS` registering a day.
+fn registry_entry(day: &str) -> String {
+    format!(
+        "    Descriptor::of::<{}::Day>(),",
+        crate_name(day)
+    )
+}
+
+/// Whether `line` registers a day crate, like
+/// `Descriptor::of::<day_01::Day>(),`.
+fn is_day_entry(line: &str) -> bool {
+    line.trim()
+        .strip_prefix("Descriptor::of::<")
+        .and_then(|rest| rest.strip_suffix("::Day>(),"))
+        .is_some_and(|name| is_day(&name.replace('_', "-")))
+}
+
+/// The runner's registry with one entry in
+/// `SOLUTIONS` per day of `days`, in place of the
+/// day entries it had.
+fn sync_registry(source: &str, days: &[String]) -> String {
+    let mut lines = Vec::new();
+    let mut in_solutions = false;
+    for line in source.lines() {
+        if line.starts_with("pub const SOLUTIONS") {
+            in_solutions = true;
+        } else if in_solutions && line == "];" {
+            lines.extend(
+                days.iter().map(|day| registry_entry(day)),
+            );
+            in_solutions = false;
+        } else if in_solutions && is_day_entry(line) {
+            continue;
+        }
+        lines.push(line.to_string());
+    }
+    lines.join("\n") + "\n"
+}
+
+/// The workspace member lists, `default-members`
+/// only when it is set.
+const MEMBER_LISTS: [&str; 2] =
+    ["members", "default-members"];
+
+/// Everything wrong with the day crates, as
+/// messages.
+fn check(
+    workspace: &Path,
+    manifest: &DocumentMut,
+) -> Vec<String> {
+    let days = day_dirs(workspace);
+    let mut problems = Vec::new();
+    for key in MEMBER_LISTS {
+        let Some(listed) = manifest["workspace"]
+            .get(key)
+            .and_then(Item::as_array)
+            .map(member_names)
+        else {
+            continue;
+        };
+        for day in &days {
+            if !listed.contains(&day.as_str()) {
+                problems.push(format!(
+                    "{day} is not in workspace.{key}"
+                ));
+            }
+        }
+        for name in listed
+            .iter()
+            .filter(|name| name.starts_with("day-"))
+        {
+            if !days.iter().any(|day| day == name) {
+                problems.push(format!(
+                    "`{name}` in workspace.{key} is not a day crate"
+                ));
+            }
+        }
+    }
+    for day in &days {
+        let manifest =
+            workspace.join(day).join("Cargo.toml");
+        let Ok(document) = fs::read_to_string(&manifest)
+            .unwrap_or_default()
+            .parse::<DocumentMut>()
+        else {
+            problems.push(format!(
+                "{} is not valid toml",
+                manifest.display()
+            ));
+            continue;
+        };
+        let name = document["package"]["name"]
+            .as_str()
+            .unwrap_or_default();
+        if name != day {
+            problems
+                .push(format!("{day} is named `{name}`"));
+        }
+        let expected = format!("{day}-bench");
+        let benches = document
+            .get("bench")
+            .and_then(|bench| bench.as_array_of_tables())
+            .map(|benches| {
+                benches
+                    .iter()
+                    .filter_map(|bench| {
+                        bench.get("name")?.as_str()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        if !benches.contains(&expected.as_str()) {
+            problems.push(format!(
+                "{day} has no bench named `{expected}`, found {benches:?}"
+            ));
+        }
+    }
+    problems.extend(check_runner(workspace, &days));
+    problems
+}
+
+/// The days the aoc runner doesn't depend on or
+/// doesn't list in its registry, as messages.
+fn check_runner(
+    workspace: &Path,
+    days: &[String],
+) -> Vec<String> {
+    let runner = workspace.join("aoc");
+    let dependencies =
+        fs::read_to_string(runner.join("Cargo.toml"))
+            .unwrap_or_default()
+            .parse::<DocumentMut>()
+            .ok()
+            .and_then(|document| {
+                Some(
+                    document
+                        .get("dependencies")?
+                        .as_table()?
+                        .clone(),
+                )
+            })
+            .unwrap_or_default();
+    let registry = fs::read_to_string(
+        runner.join("src").join("registry.rs"),
+    )
+    .unwrap_or_default();
+    let mut problems = Vec::new();
+    for day in days {
+        if !dependencies.contains_key(day) {
+            problems.push(format!(
+                "{day} is not a dependency of aoc/Cargo.toml"
+            ));
+        }
+        let entry = registry_entry(day);
+        if !registry.lines().any(|line| line == entry) {
+            problems.push(format!(
+                "{day} is not registered in aoc/src/registry.rs"
+            ));
+        }
+    }
+    problems
+}
+
+fn main() {
+    let args = Args::parse();
+    let workspace = &args.current_working_directory;
+    let manifest = workspace.join("Cargo.toml");
+    let mut document = read_manifest(&manifest);
+
+    match args.command {
+        Command::Sync => {
+            let days = day_dirs(workspace);
+            let mut changed = false;
+            for key in MEMBER_LISTS {
+                if let Some(members) = document["workspace"]
+                    .get_mut(key)
+                    .and_then(Item::as_array_mut)
+                {
+                    changed |= sync_members(members, &days);
+                }
+            }
+            if changed {
+                write(&manifest, &document.to_string());
+            }
+
+            let runner =
+                workspace.join("aoc").join("Cargo.toml");
+            let mut document = read_manifest(&runner);
+            let dependencies = document["dependencies"]
+                .as_table_mut()
+                .expect("runner should have dependencies");
+            if sync_dependencies(dependencies, &days) {
+                write(&runner, &document.to_string());
+                changed = true;
+            }
+
+            let registry = workspace
+                .join("aoc")
+                .join("src")
+                .join("registry.rs");
+            let source = fs::read_to_string(&registry)
+                .expect("should be able to read registry");
+            let synced = sync_registry(&source, &days);
+            if synced != source {
+                write(&registry, &synced);
+                changed = true;
+            }
+            if !changed {
+                println!("workspace is up to date");
+            }
+        }
+        Command::Check => {
+            let problems = check(workspace, &document);
+            for problem in &problems {
+                eprintln!("{problem}");
+            }
+            if !problems.is_empty() {
+                std::process::exit(1);
+            }
+            println!("workspace is consistent");
+        }
+    }
+}
+
+fn read_manifest(path: &Path) -> DocumentMut {
+    fs::read_to_string(path)
+        .expect("should be able to read the manifest")
+        .parse::<DocumentMut>()
+        .expect("manifest should be valid toml")
+}
+
+fn write(path: &Path, contents: &str) {
+    fs::write(path, contents)
+        .expect("should be able to write the file");
+    println!("updated {}", path.display());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn days(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    /// A scratch directory holding `files`,
+    /// removed when dropped.
+    struct Scratch(PathBuf);
+
+    impl Scratch {
+        fn new(name: &str, files: &[(&str, &str)]) -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "workspace-{name}-{}",
+                std::process::id()
+            ));
+            for (path, contents) in files {
+                let path = dir.join(path);
+                fs::create_dir_all(path.parent().unwrap())
+                    .unwrap();
+                fs::write(path, contents).unwrap();
+            }
+            Scratch(dir)
+        }
+    }
+
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_is_day() {
+        assert!(is_day("day-01"));
+        assert!(is_day("day-25"));
+        assert!(!is_day("day-1"));
+        assert!(!is_day("day-001"));
+        assert!(!is_day("day-*"));
+        assert!(!is_day("day-0a"));
+        assert!(!is_day("aoc"));
+    }
+
+    #[test]
+    fn test_day_dirs() {
+        let scratch = Scratch::new(
+            "day-dirs",
+            &[
+                ("day-02/Cargo.toml", ""),
+                ("day-01/Cargo.toml", ""),
+                ("day-03/src/lib.rs", ""),
+                ("day-4/Cargo.toml", ""),
+                ("aoc/Cargo.toml", ""),
+            ],
+        );
+        assert_eq!(
+            day_dirs(&scratch.0),
+            ["day-01", "day-02"]
+        );
+    }
+
+    #[test]
+    fn test_sync_members() {
+        let mut document = r#"[workspace]
+members = ["aoc", "day-*", "day-01", "day-03"]
+"#
+        .parse::<DocumentMut>()
+        .unwrap();
+        let members = document["workspace"]["members"]
+            .as_array_mut()
+            .unwrap();
+        let days = days(&["day-01", "day-02"]);
+        assert!(sync_members(members, &days));
+        assert!(!sync_members(members, &days));
+        assert_eq!(
+            document.to_string(),
+            r#"[workspace]
+members = [
+    "aoc",
+    "day-01",
+    "day-02",
+]
+"#
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        let day = r#"[package]
+name = "day-01"
+
+[[bench]]
+name = "day-01-bench"
+"#;
+        let scratch = Scratch::new(
+            "check",
+            &[
+                ("day-01/Cargo.toml", day),
+                ("day-02/Cargo.toml", &day.replace("01", "02")),
+                (
+                    "aoc/Cargo.toml",
+                    "[dependencies]\nday-01 = { path = \"../day-01\" }\n",
+                ),
+                (
+                    "aoc/src/registry.rs",
+                    "pub const SOLUTIONS: &[Descriptor] = &[
+    Descriptor::of::<day_01::Day>(),
+];
+",
+                ),
+            ],
+        );
+        let manifest = r#"[workspace]
+members = ["aoc", "day-01", "day-02"]
+default-members = ["aoc", "day-*"]
+"#
+        .parse::<DocumentMut>()
+        .unwrap();
+        assert_eq!(
+            check(&scratch.0, &manifest),
+            [
+                "day-01 is not in workspace.default-members",
+                "day-02 is not in workspace.default-members",
+                "`day-*` in workspace.default-members is not a day crate",
+                "day-02 is not a dependency of aoc/Cargo.toml",
+                "day-02 is not registered in aoc/src/registry.rs",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sync_dependencies() {
+        let mut document = r#"[dependencies]
+clap.workspace = true
+day-01 = { path = "../day-01" }
+day-03 = { path = "../day-03" }
+"#
+        .parse::<DocumentMut>()
+        .unwrap();
+        let dependencies = document["dependencies"]
+            .as_table_mut()
+            .unwrap();
+        assert!(sync_dependencies(
+            dependencies,
+            &days(&["day-01", "day-02"])
+        ));
+        assert!(!sync_dependencies(
+            dependencies,
+            &days(&["day-01", "day-02"])
+        ));
+        assert_eq!(
+            document.to_string(),
+            r#"[dependencies]
+clap.workspace = true
+day-01 = { path = "../day-01" }
+day-02 = { path = "../day-02" }
+"#
+        );
+    }
+
+    #[test]
+    fn test_sync_registry() {
+        let source = "use aoc_core::Descriptor;
+
+pub const SOLUTIONS: &[Descriptor] = &[
+    Descriptor::of::<day_01::Day>(),
+    Descriptor::of::<day_03::Day>(),
+];
+
+fn other() {}
+";
+        let days = days(&["day-01", "day-02"]);
+        let synced = sync_registry(source, &days);
+        assert_eq!(
+            synced,
+            "use aoc_core::Descriptor;
+
+pub const SOLUTIONS: &[Descriptor] = &[
+    Descriptor::of::<day_01::Day>(),
+    Descriptor::of::<day_02::Day>(),
+];
+
+fn other() {}
+"
+        );
+        assert_eq!(sync_registry(&synced, &days), synced);
+    }
+}